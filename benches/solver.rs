@@ -0,0 +1,37 @@
+// Solver Benchmarks
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rust_sudoku::fixtures;
+use rust_sudoku::generator::generate_seeded;
+use rust_sudoku::sudoku_solver::{BacktrackingSolver, DancingLinksSolver, LogicSolver};
+use std::hint::black_box;
+
+// Each solver on every fixture, easiest to hardest.
+fn bench_solvers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve");
+    for (name, line) in fixtures::ALL {
+        let board = fixtures::board(line);
+        group.bench_with_input(BenchmarkId::new("backtracking", name), &board, |b, board| {
+            b.iter(|| BacktrackingSolver::run(black_box(board)))
+        });
+        group.bench_with_input(BenchmarkId::new("dancing_links", name), &board, |b, board| {
+            b.iter(|| DancingLinksSolver::run(black_box(board)))
+        });
+        group.bench_with_input(BenchmarkId::new("dancing_links_arena", name), &board, |b, board| {
+            let mut solver = DancingLinksSolver::with_arena();
+            b.iter(|| solver.solve(black_box(board)))
+        });
+        group.bench_with_input(BenchmarkId::new("logic", name), &board, |b, board| {
+            b.iter(|| LogicSolver::solve(&mut black_box(*board)))
+        });
+    }
+    group.finish();
+}
+
+// Generating a puzzle down to a typical clue count. The seed is fixed, so every iteration
+// and every run generates the same puzzle and timings are comparable.
+fn bench_generate(c: &mut Criterion) {
+    c.bench_function("generate_30_clues", |b| b.iter(|| generate_seeded(black_box(30), black_box(42))));
+}
+
+criterion_group!(benches, bench_solvers, bench_generate);
+criterion_main!(benches);
@@ -0,0 +1,283 @@
+// Candidate Grid Module
+use crate::error::SudokuError;
+use crate::sudoku_board::SudokuBoard;
+use alloc::vec::Vec;
+
+/// Candidates a deduction rules out: `value` can't go in any of `cells`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elimination {
+    pub value: u8,
+    pub cells: Vec<(u8, u8)>,
+}
+
+/// A board together with the candidates still open in each empty cell, kept as bitmasks.
+/// Unlike `SudokuBoard::candidates`, which rereads the row, column and box every time,
+/// eliminations made here stick, so deduction techniques can build on each other's work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CandidateGrid {
+    board: SudokuBoard,
+    // masks[r][c] has bit n set while n is still a candidate for (r, c). Filled cells have none.
+    masks: [[u16; 9]; 9],
+}
+
+impl CandidateGrid {
+    /// Starts from the board's filled cells, with every number its peers allow as a
+    /// candidate for each empty cell.
+    pub fn new(board: &SudokuBoard) -> Self {
+        let masks = core::array::from_fn(|r| core::array::from_fn(|c| board.candidates_mask((r as u8, c as u8))));
+        CandidateGrid { board: *board, masks }
+    }
+
+    /// The board with every number assigned so far.
+    pub fn board(&self) -> &SudokuBoard {
+        &self.board
+    }
+
+    /// The board, if every cell has been filled without breaking a rule.
+    pub fn solved_board(&self) -> Option<SudokuBoard> {
+        self.board.is_solved().then_some(self.board)
+    }
+
+    /// The numbers still possible for a cell, in ascending order. Empty for a filled cell or
+    /// one off the board.
+    pub fn candidates(&self, cell: (u8, u8)) -> Vec<u8> {
+        let mask = self.candidates_mask(cell);
+        (1..=9).filter(|&num| mask & (1 << num) != 0).collect()
+    }
+
+    /// The candidates as a bitmask, with bit n set if n is still possible. 0 for a filled
+    /// cell or one off the board.
+    pub fn candidates_mask(&self, cell: (u8, u8)) -> u16 {
+        self.masks
+            .get(cell.0 as usize)
+            .and_then(|row| row.get(cell.1 as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Rules `value` out for a cell. Returns true if it was a candidate until now.
+    pub fn eliminate(&mut self, cell: (u8, u8), value: u8) -> bool {
+        if cell.0 >= 9 || cell.1 >= 9 || !(1..=9).contains(&value) {
+            return false;
+        }
+        self.restrict(cell, !(1 << value))
+    }
+
+    /// Fills a cell with `value` and rules it out for all 20 peers. The value must still be a
+    /// candidate; otherwise the grid is left unchanged.
+    pub fn assign(&mut self, cell: (u8, u8), value: u8) -> Result<(), SudokuError> {
+        if cell.0 >= 9 || cell.1 >= 9 {
+            return Err(SudokuError::OutOfBounds { row: cell.0, col: cell.1 });
+        }
+        if self.board.get(cell) != Some(0) {
+            return Err(SudokuError::CellFilled);
+        }
+        if !(1..=9).contains(&value) {
+            return Err(SudokuError::InvalidValue(value));
+        }
+        if self.candidates_mask(cell) & (1 << value) == 0 {
+            return Err(SudokuError::Conflict);
+        }
+
+        self.board.place_fast(cell, value);
+        self.masks[cell.0 as usize][cell.1 as usize] = 0;
+        for peer in SudokuBoard::peers(cell).unwrap() {
+            self.restrict(peer, !(1 << value));
+        }
+        Ok(())
+    }
+
+    /// Finds an elimination by simple coloring. For each number, cells joined by conjugate
+    /// pairs (the only two spots left for it in some unit) alternate between holding it and
+    /// not, so each chain splits into two colors, exactly one of which is true. A color that
+    /// shows up twice in one unit must be the false one, and a cell that sees both colors
+    /// can't hold the number either way. Returns None if neither rule removes a candidate.
+    pub fn find_simple_coloring(&self) -> Option<Elimination> {
+        let units: Vec<_> = self.board.units().map(|(_, _, cells)| cells).collect();
+        for value in 1..=9 {
+            let has = |cell: (u8, u8)| self.candidates_mask(cell) & (1 << value) != 0;
+            let links: Vec<[(u8, u8); 2]> = units
+                .iter()
+                .filter_map(|cells| {
+                    let mut spots = cells.iter().copied().filter(|&cell| has(cell));
+                    match (spots.next(), spots.next(), spots.next()) {
+                        (Some(a), Some(b), None) => Some([a, b]),
+                        _ => None,
+                    }
+                })
+                .collect();
+
+            let mut colored = [[false; 9]; 9];
+            for start in links.iter().map(|link| link[0]) {
+                if colored[start.0 as usize][start.1 as usize] {
+                    continue;
+                }
+                // Walk the chain through `start`, giving each cell the opposite color to the
+                // one it was reached from.
+                let mut chain = vec![(start, true)];
+                colored[start.0 as usize][start.1 as usize] = true;
+                let mut next = 0;
+                while let Some(&(cell, color)) = chain.get(next) {
+                    next += 1;
+                    for link in &links {
+                        let other = match *link {
+                            [a, b] if a == cell => b,
+                            [a, b] if b == cell => a,
+                            _ => continue,
+                        };
+                        if !colored[other.0 as usize][other.1 as usize] {
+                            colored[other.0 as usize][other.1 as usize] = true;
+                            chain.push((other, !color));
+                        }
+                    }
+                }
+
+                for color in [true, false] {
+                    let cells: Vec<_> = chain.iter().filter(|link| link.1 == color).map(|link| link.0).collect();
+                    let wraps = cells.iter().enumerate().any(|(i, &a)| {
+                        cells[i + 1..].iter().any(|&b| SudokuBoard::is_peer(a, b))
+                    });
+                    if wraps {
+                        return Some(Elimination { value, cells });
+                    }
+                }
+
+                let sees = |cell, color| chain.iter().any(|&(other, c)| c == color && SudokuBoard::is_peer(cell, other));
+                let cells: Vec<_> = self
+                    .board
+                    .iter_empty()
+                    .filter(|&cell| has(cell) && !chain.iter().any(|link| link.0 == cell))
+                    .filter(|&cell| sees(cell, true) && sees(cell, false))
+                    .collect();
+                if !cells.is_empty() {
+                    return Some(Elimination { value, cells });
+                }
+            }
+        }
+        None
+    }
+
+    // Narrows a cell's candidates to those in `keep`. Returns true if any were removed.
+    // Assumes `cell` is on the board.
+    pub(crate) fn restrict(&mut self, cell: (u8, u8), keep: u16) -> bool {
+        let mask = &mut self.masks[cell.0 as usize][cell.1 as usize];
+        let before = *mask;
+        *mask &= keep;
+        *mask != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle() -> SudokuBoard {
+        crate::fixtures::board(crate::fixtures::SINGLES)
+    }
+
+    #[test]
+    fn test_new_matches_board_candidates() {
+        let board = puzzle();
+        let grid = CandidateGrid::new(&board);
+        for (cell, _) in board.iter_cells() {
+            assert_eq!(grid.candidates(cell), board.candidates(cell));
+        }
+        assert_eq!(grid.candidates((9, 0)), Vec::<u8>::new());
+        assert_eq!(grid.board(), &board);
+    }
+
+    #[test]
+    fn test_eliminate() {
+        let mut grid = CandidateGrid::new(&puzzle());
+        let before = grid.candidates((0, 0));
+        assert!(before.contains(&3));
+        assert!(grid.eliminate((0, 0), 3));
+        assert!(!grid.eliminate((0, 0), 3));
+        assert!(!grid.candidates((0, 0)).contains(&3));
+        assert_eq!(grid.candidates((0, 0)).len(), before.len() - 1);
+
+        // Nothing to remove from a filled cell, off the board, or for a value out of range.
+        assert!(!grid.eliminate((0, 2), 6));
+        assert!(!grid.eliminate((9, 0), 3));
+        assert!(!grid.eliminate((0, 0), 0));
+    }
+
+    #[test]
+    fn test_assign_clears_peers() {
+        let mut grid = CandidateGrid::new(&puzzle());
+        let num = grid.candidates((0, 0))[0];
+        assert_eq!(grid.assign((0, 0), num), Ok(()));
+
+        assert_eq!(grid.board().get((0, 0)), Some(num));
+        assert_eq!(grid.candidates_mask((0, 0)), 0);
+        for peer in SudokuBoard::peers((0, 0)).unwrap() {
+            assert!(!grid.candidates(peer).contains(&num));
+        }
+    }
+
+    #[test]
+    fn test_assign_errors() {
+        let mut grid = CandidateGrid::new(&puzzle());
+        grid.eliminate((0, 0), 3);
+        let before = grid;
+
+        assert_eq!(grid.assign((9, 0), 1), Err(SudokuError::OutOfBounds { row: 9, col: 0 }));
+        assert_eq!(grid.assign((0, 2), 6), Err(SudokuError::CellFilled));
+        assert_eq!(grid.assign((0, 0), 10), Err(SudokuError::InvalidValue(10)));
+        assert_eq!(grid.assign((0, 0), 3), Err(SudokuError::Conflict));
+        assert_eq!(grid.assign((0, 0), 6), Err(SudokuError::Conflict));
+        assert_eq!(grid, before);
+    }
+
+    #[test]
+    fn test_find_simple_coloring() {
+        // No naked or hidden single is left here, but the 1s form a chain of conjugate pairs
+        // that (4,0), (5,0) and (5,6) all see both colors of.
+        let stalled = [
+            [0, 7, 9, 0, 0, 0, 4, 3, 5],
+            [0, 4, 2, 9, 5, 3, 6, 7, 0],
+            [0, 3, 5, 0, 7, 0, 9, 0, 0],
+            [7, 8, 4, 1, 9, 2, 3, 5, 6],
+            [0, 9, 0, 0, 3, 0, 0, 0, 7],
+            [0, 0, 3, 0, 0, 7, 0, 0, 9],
+            [4, 5, 0, 7, 0, 8, 0, 9, 3],
+            [3, 0, 7, 0, 4, 9, 5, 0, 0],
+            [9, 2, 8, 3, 1, 5, 7, 6, 4],
+        ];
+        let board = SudokuBoard::from(stalled).unwrap();
+        let grid = CandidateGrid::new(&board);
+        for (_, _, cells) in board.units() {
+            for cell in cells.into_iter().filter(|&cell| board.get(cell) == Some(0)) {
+                assert!(grid.candidates(cell).len() > 1);
+            }
+            for num in 1..=9 {
+                let spots = cells.iter().filter(|&&cell| grid.candidates(cell).contains(&num));
+                assert_ne!(spots.count(), 1);
+            }
+        }
+
+        let found = grid.find_simple_coloring().unwrap();
+        assert_eq!(found, Elimination { value: 1, cells: vec![(4, 0), (5, 0), (5, 6)] });
+        let solved = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        for cell in found.cells {
+            assert_ne!(solved.get(cell), Some(1));
+        }
+
+        // A solved board has no candidates left to color.
+        assert_eq!(CandidateGrid::new(&solved).find_simple_coloring(), None);
+    }
+
+    #[test]
+    fn test_solved_board() {
+        let board = puzzle();
+        let mut grid = CandidateGrid::new(&board);
+        assert_eq!(grid.solved_board(), None);
+
+        // Assigning the solution cell by cell always finds the value still a candidate.
+        let solution = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        for cell in board.iter_empty() {
+            grid.assign(cell, solution.get(cell).unwrap()).unwrap();
+        }
+        assert_eq!(grid.solved_board(), Some(solution));
+    }
+}
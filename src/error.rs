@@ -0,0 +1,91 @@
+// Error Module
+use core::fmt;
+
+/// Errors returned when building or changing a `SudokuBoard`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SudokuError {
+    /// The grid breaks a Sudoku rule, so no board can be built from it.
+    InvalidConfig,
+    /// The cell is not on the 9x9 board.
+    OutOfBounds { row: u8, col: u8 },
+    /// The cell holds a starting number, which can't be changed.
+    CannotModifyInitial,
+    /// The value is not a number from 1 to 9 (or 0 where clearing is allowed).
+    InvalidValue(u8),
+    /// The cell is already filled; it must be cleared before placing a different number.
+    CellFilled,
+    /// The number already appears in the cell's row, column or box.
+    Conflict,
+    /// A puzzle string did not contain exactly 81 cells.
+    InvalidLength,
+    /// A puzzle string contained a character that isn't a cell.
+    InvalidCharacter(char),
+    /// A row, column or unit index is not 0-8.
+    InvalidIndex(u8),
+    /// The two columns are in different vertical stacks.
+    DifferentStacks,
+    /// A digit mapping is not a permutation of 1-9.
+    InvalidMapping,
+    /// The board has no solution.
+    Unsolvable,
+    /// A text grid could not be read at this line and column (both counted from 1).
+    InvalidGrid { line: usize, column: usize },
+}
+
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SudokuError::InvalidConfig => write!(f, "Error: Invalid config, a row, column or box repeats a number."),
+            SudokuError::OutOfBounds { row, col } => write!(f, "Error: Cell ({}, {}) is out of bounds.", row, col),
+            SudokuError::CannotModifyInitial => write!(f, "Error: Cannot modify a starting number."),
+            SudokuError::InvalidValue(num) => write!(f, "Error: Number must be between 1 and 9, got {}.", num),
+            SudokuError::CellFilled => write!(f, "Error: Cannot overwrite filled cell. Try clearing first."),
+            SudokuError::Conflict => write!(f, "Error: Move conflicts with another number."),
+            SudokuError::InvalidLength => write!(f, "Error: Puzzle string must contain exactly 81 cells."),
+            SudokuError::InvalidCharacter(ch) => write!(f, "Error: Invalid character {:?} in puzzle string.", ch),
+            SudokuError::InvalidIndex(index) => write!(f, "Error: Index {} is out of bounds.", index),
+            SudokuError::DifferentStacks => write!(f, "Error: Columns must be in the same stack."),
+            SudokuError::InvalidMapping => write!(f, "Error: Mapping must be a permutation of 1-9."),
+            SudokuError::Unsolvable => write!(f, "Error: The board has no solution."),
+            SudokuError::InvalidGrid { line, column } => {
+                write!(f, "Error: Invalid puzzle grid at line {}, column {}.", line, column)
+            }
+        }
+    }
+}
+
+impl core::error::Error for SudokuError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku_board::SudokuBoard;
+
+    #[test]
+    fn test_display_includes_details() {
+        assert_eq!(SudokuError::InvalidCharacter('x').to_string(), "Error: Invalid character 'x' in puzzle string.");
+        assert_eq!(
+            SudokuError::OutOfBounds { row: 9, col: 2 }.to_string(),
+            "Error: Cell (9, 2) is out of bounds."
+        );
+        assert!(SudokuError::InvalidValue(10).to_string().contains("10"));
+    }
+
+    #[test]
+    fn test_converts_to_boxed_error() {
+        fn parse(line: &str) -> Result<SudokuBoard, Box<dyn std::error::Error>> {
+            Ok(line.parse()?)
+        }
+        let err = parse("123").err().unwrap();
+        assert_eq!(err.downcast_ref::<SudokuError>(), Some(&SudokuError::InvalidLength));
+        assert!(parse(&"0".repeat(81)).is_ok());
+
+        fn place() -> Result<(), Box<dyn std::error::Error>> {
+            let mut board = SudokuBoard::from([[0; 9]; 9])?;
+            board.try_place((9, 9), 1)?;
+            Ok(())
+        }
+        let err = place().unwrap_err();
+        assert_eq!(err.downcast_ref::<SudokuError>(), Some(&SudokuError::OutOfBounds { row: 9, col: 9 }));
+    }
+}
@@ -0,0 +1,69 @@
+// Fixtures Module
+use crate::sudoku_board::SudokuBoard;
+
+// Sample puzzles spanning the difficulty range, shared by the tests and the benchmarks.
+// Each is an 81-character line with `0` for empty cells and has exactly one solution.
+
+/// Naked and hidden singles are enough.
+pub const SINGLES: &str = "006040097040730010017092030600070080105060903020010006050980160090056070860020300";
+/// Singles stall until naked or hidden pairs clear the way.
+pub const PAIRS: &str = "009700032050200040000009000000008000040590010080001005003000060600980000007020000";
+/// Singles stall until a naked pair in column 0 clears the way.
+pub const NAKED_PAIR: &str = "800002000007010500000007300090600003700000050048250900000000060650000000004890200";
+/// Singles stall until a hidden pair in row 3 clears the way.
+pub const HIDDEN_PAIR: &str = "001240300400006000000003105000082000020000071000000090900635008000000010506000700";
+/// Singles and pairs stall, but box/line intersections see it through.
+pub const INTERSECTIONS: &str = "002001900000600000060003040004800500000500000000024370005300409300940806090000000";
+/// Singles and pairs stall until the 9s in a box point along column 0.
+pub const POINTING: &str = "030400000020100830000009107004600000600005700100000500000000200008700060740300008";
+/// Singles and pairs stall until the 3s in a line are confined to the top-left box.
+pub const BOX_LINE: &str = "000005240800002090045000000500900007700301002008000060400000100000067059090000000";
+/// Everything up to intersections stalls until a naked triple in column 5 clears the way.
+pub const NAKED_TRIPLE: &str = "000070040200600000709820000600010720800500000000000063030740800100000000000000906";
+/// Everything up to intersections stalls until a hidden triple clears the way.
+pub const HIDDEN_TRIPLE: &str = "000000000800209306500400018000800000000090472000072600000007009020500000043006000";
+/// Every easier technique stalls until an X-Wing removes a candidate.
+pub const X_WING: &str = "400000000005600000098000035000000009000001040050024700000060904302900067006200001";
+/// Everything up to X-Wings stalls until a Swordfish on the 5s removes a candidate.
+pub const SWORDFISH: &str = "300009000001020030070300402060908000000060050000000640086700000029000005005800070";
+/// Everything up to Swordfish stalls; simple coloring is the only way forward.
+pub const COLORING: &str = "000000405042900000005070900700100306000030007000000000400008003307049500920010700";
+/// Arto Inkala's "world's hardest Sudoku", which no technique here solves without guessing.
+pub const HARDEST: &str = "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+/// Every fixture, easiest first, paired with a short name for reports.
+pub const ALL: [(&str, &str); 13] = [
+    ("singles", SINGLES),
+    ("pairs", PAIRS),
+    ("naked_pair", NAKED_PAIR),
+    ("hidden_pair", HIDDEN_PAIR),
+    ("intersections", INTERSECTIONS),
+    ("pointing", POINTING),
+    ("box_line", BOX_LINE),
+    ("naked_triple", NAKED_TRIPLE),
+    ("hidden_triple", HIDDEN_TRIPLE),
+    ("x_wing", X_WING),
+    ("swordfish", SWORDFISH),
+    ("coloring", COLORING),
+    ("hardest", HARDEST),
+];
+
+/// Parses a fixture line into a board.
+///
+/// Panics if `line` is not a valid puzzle, which never happens for the lines above.
+pub fn board(line: &str) -> SudokuBoard {
+    line.parse().expect("fixture is a valid puzzle")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku_solver::BacktrackingSolver;
+
+    #[test]
+    fn test_fixtures_have_unique_solutions() {
+        for (name, line) in ALL {
+            assert_eq!(BacktrackingSolver::count_solutions(&board(line), 2), 1, "{}", name);
+        }
+    }
+}
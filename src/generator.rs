@@ -0,0 +1,335 @@
+// Puzzle Generator Module
+use crate::rng::Rng;
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::BacktrackingSolver;
+#[cfg(feature = "extra_solvers")]
+use crate::sudoku_solver::{Difficulty, rate_difficulty, solves_within};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Generates a puzzle with a unique solution and roughly `clues` starting numbers.
+///
+/// A random complete grid is filled first, then cells are emptied one at a time in random
+/// order, keeping each removal only if the puzzle still has exactly one solution. If no
+/// further cell can be removed the result has more than `clues` givens.
+///
+/// Seeds from the system clock, so it needs the `std` feature; `generate_seeded` works
+/// everywhere.
+#[cfg(feature = "std")]
+pub fn generate(clues: usize) -> SudokuBoard {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or(0);
+    generate_from(clues, &mut Rng::new(seed))
+}
+
+/// Like `generate`, but fully determined by `seed`: the same `clues` and `seed` always
+/// produce the same puzzle.
+///
+/// The seed drives a xorshift64* generator (initialized through one splitmix64 step) that
+/// is implemented in this crate, so seeds stay stable across patch releases and don't
+/// depend on any external RNG crate.
+pub fn generate_seeded(clues: usize, seed: u64) -> SudokuBoard {
+    generate_from(clues, &mut Rng::new(seed))
+}
+
+/// Like `generate_seeded`, but also returns the puzzle's solution: the grid it was carved
+/// from, with every number the puzzle leaves out filled in as a player entry. It comes out
+/// of generation anyway, so there is no need to solve the puzzle again.
+pub fn generate_with_solution(clues: usize, seed: u64) -> (SudokuBoard, SudokuBoard) {
+    generate_solved(clues, Symmetry::None, &mut Rng::new(seed))
+}
+
+/// Generates a puzzle that `rate_difficulty` rates exactly `difficulty`, fully determined by
+/// `seed`. Cells are emptied in random order as for `generate_seeded`, but a removal is
+/// kept only if the puzzle still has one solution and the techniques `difficulty` allows
+/// still solve it without guessing; an `Easy` puzzle never needs more than naked singles.
+/// A grid that carves down to an easier rating is thrown away and another one carved.
+/// Returns None for `NotUnique`.
+#[cfg(feature = "extra_solvers")]
+pub fn generate_with_difficulty(difficulty: Difficulty, seed: u64) -> Option<SudokuBoard> {
+    if difficulty == Difficulty::NotUnique {
+        return None;
+    }
+
+    let mut rng = Rng::new(seed);
+    loop {
+        let mut grid = fill_grid(&mut rng);
+        let mut cells: Vec<usize> = (0..81).collect();
+        rng.shuffle(&mut cells);
+        for index in cells {
+            let (r, c) = (index / 9, index % 9);
+            let removed = grid[r][c];
+            grid[r][c] = 0;
+            let board = SudokuBoard::from(grid).expect("removing a number keeps the config valid");
+            if BacktrackingSolver::count_solutions(&board, 2) != 1 || !solves_within(&board, difficulty) {
+                grid[r][c] = removed;
+            }
+        }
+
+        let puzzle = SudokuBoard::from(grid).expect("a carved solution is always a valid config");
+        if rate_difficulty(&puzzle) == difficulty {
+            return Some(puzzle);
+        }
+    }
+}
+
+/// Which symmetry the pattern of starting numbers keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Any pattern.
+    None,
+    /// Unchanged by a half turn: (r, c) pairs with (8 - r, 8 - c).
+    Rotational180,
+    /// Mirrored top to bottom: (r, c) pairs with (8 - r, c).
+    Horizontal,
+    /// Mirrored left to right: (r, c) pairs with (r, 8 - c).
+    Vertical,
+    /// Mirrored across the main diagonal: (r, c) pairs with (c, r).
+    Diagonal,
+}
+
+impl Symmetry {
+    // The cell paired with (r, c).
+    fn mirror(self, (r, c): (usize, usize)) -> (usize, usize) {
+        match self {
+            Symmetry::None => (r, c),
+            Symmetry::Rotational180 => (8 - r, 8 - c),
+            Symmetry::Horizontal => (8 - r, c),
+            Symmetry::Vertical => (r, 8 - c),
+            Symmetry::Diagonal => (c, r),
+        }
+    }
+}
+
+/// Like `generate_seeded`, but cells are emptied together with their counterpart under
+/// `symmetry`, so the starting numbers form a symmetric pattern. Pairs that can't both go
+/// are kept, and so is a pair that would take the count below `clues`, so the result may
+/// have a few more than `clues` givens but never fewer.
+pub fn generate_with(clues: usize, symmetry: Symmetry, seed: u64) -> SudokuBoard {
+    generate_from_with(clues, symmetry, &mut Rng::new(seed))
+}
+
+fn generate_from(clues: usize, rng: &mut Rng) -> SudokuBoard {
+    generate_from_with(clues, Symmetry::None, rng)
+}
+
+fn generate_from_with(clues: usize, symmetry: Symmetry, rng: &mut Rng) -> SudokuBoard {
+    generate_solved(clues, symmetry, rng).0
+}
+
+// Generates a puzzle together with the grid it was carved from.
+fn generate_solved(clues: usize, symmetry: Symmetry, rng: &mut Rng) -> (SudokuBoard, SudokuBoard) {
+    let full = fill_grid(rng);
+    let mut grid = full;
+    carve(&mut grid, clues, symmetry, rng);
+    let puzzle = SudokuBoard::from(grid).expect("a carved solution is always a valid config");
+
+    let mut solution = puzzle;
+    for (r, row) in full.iter().enumerate() {
+        for (c, &value) in row.iter().enumerate() {
+            if grid[r][c] == 0 {
+                solution.place_fast((r as u8, c as u8), value);
+            }
+        }
+    }
+    (puzzle, solution)
+}
+
+// A random complete grid: the empty board solved with numbers tried in an order seeded
+// from `rng`.
+fn fill_grid(rng: &mut Rng) -> [[u8; 9]; 9] {
+    let solved = BacktrackingSolver::run_random(&SudokuBoard::empty(), rng.next_u64())
+        .expect("the empty board always has a solution");
+    *solved.get_board()
+}
+
+/// Removes every clue that isn't needed: the result has the same single solution as
+/// `board`, but clearing any one of its remaining cells would allow another. All filled
+/// cells, starting numbers or not, are treated as clues and become the result's givens.
+/// Boards without exactly one solution are returned unchanged.
+pub fn minimize(board: &SudokuBoard) -> SudokuBoard {
+    if BacktrackingSolver::count_solutions(board, 2) != 1 {
+        return *board;
+    }
+    let mut grid = *board.get_board();
+    let cells: Vec<usize> = (0..81).collect();
+    remove_while_unique(&mut grid, &cells, 0, Symmetry::None);
+    SudokuBoard::from(grid).expect("removing numbers keeps the config valid")
+}
+
+// Empties cells in random order while the puzzle keeps a unique solution, stopping once
+// only `clues` filled cells remain.
+fn carve(grid: &mut [[u8; 9]; 9], clues: usize, symmetry: Symmetry, rng: &mut Rng) {
+    let mut cells: Vec<usize> = (0..81).collect();
+    rng.shuffle(&mut cells);
+    remove_while_unique(grid, &cells, clues, symmetry);
+}
+
+// Tries emptying each of `cells` (row-major indices) together with its counterpart under
+// `symmetry`, keeping a removal only if the puzzle still has a unique solution. Never goes
+// below `clues` filled cells, and stops once exactly that many remain.
+fn remove_while_unique(grid: &mut [[u8; 9]; 9], cells: &[usize], clues: usize, symmetry: Symmetry) {
+    let mut filled = grid.as_flattened().iter().filter(|&&value| value != 0).count();
+    for &index in cells {
+        if filled <= clues {
+            break;
+        }
+        let (r, c) = (index / 9, index % 9);
+        let (mr, mc) = symmetry.mirror((r, c));
+        let removed = (grid[r][c], grid[mr][mc]);
+        let count = if (r, c) == (mr, mc) { 1 } else { 2 };
+        if removed.0 == 0 || removed.1 == 0 || filled - count < clues {
+            continue;
+        }
+        grid[r][c] = 0;
+        grid[mr][mc] = 0;
+
+        let board = SudokuBoard::from(*grid).expect("removing a number keeps the config valid");
+        if BacktrackingSolver::count_solutions(&board, 2) == 1 {
+            filled -= count;
+        } else {
+            grid[r][c] = removed.0;
+            grid[mr][mc] = removed.1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_grid_completes_a_valid_grid() {
+        let grid = fill_grid(&mut Rng::new(7));
+        assert!(!grid.as_flattened().contains(&0));
+        assert!(SudokuBoard::is_valid_config(&grid));
+    }
+
+    #[test]
+    fn test_generate_seeded_is_reproducible() {
+        let first = generate_seeded(30, 42);
+        let second = generate_seeded(30, 42);
+        assert_eq!(first.to_line(), second.to_line());
+        assert_eq!(BacktrackingSolver::count_solutions(&first, 2), 1);
+
+        assert_ne!(first.to_line(), generate_seeded(30, 43).to_line());
+    }
+
+    #[test]
+    fn test_generate_with_symmetry() {
+        let symmetries = [Symmetry::Rotational180, Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Diagonal];
+        for (seed, symmetry) in symmetries.into_iter().enumerate() {
+            let board = generate_with(30, symmetry, seed as u64);
+            assert_eq!(BacktrackingSolver::count_solutions(&board, 2), 1);
+            for r in 0..9 {
+                for c in 0..9 {
+                    let (mr, mc) = symmetry.mirror((r, c));
+                    let given = |r: usize, c: usize| board.is_given((r as u8, c as u8));
+                    assert_eq!(given(r, c), given(mr, mc), "{:?} breaks at ({}, {})", symmetry, r, c);
+                }
+            }
+        }
+
+        // Without symmetry it is the same puzzle generate_seeded makes.
+        assert_eq!(generate_with(30, Symmetry::None, 42), generate_seeded(30, 42));
+    }
+
+    #[test]
+    fn test_generate_with_never_undershoots() {
+        let symmetries =
+            [Symmetry::None, Symmetry::Rotational180, Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Diagonal];
+        for symmetry in symmetries {
+            for clues in [26, 30, 35] {
+                for seed in 0..5 {
+                    let board = generate_with(clues, symmetry, seed);
+                    assert!(board.filled_count() >= clues, "{:?}, {} clues, seed {}", symmetry, clues, seed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimize() {
+        let puzzle = generate_seeded(40, 5);
+        let minimal = minimize(&puzzle);
+        assert!(minimal.given_count() <= puzzle.given_count());
+        assert_eq!(BacktrackingSolver::count_solutions(&minimal, 2), 1);
+        let solved = BacktrackingSolver::run(&puzzle).unwrap();
+        assert_eq!(BacktrackingSolver::run(&minimal).unwrap().get_board(), solved.get_board());
+
+        // Every clue left is needed.
+        for (cell, value) in minimal.iter_cells().filter(|&(_, value)| value != 0) {
+            let mut grid = *minimal.get_board();
+            grid[cell.0 as usize][cell.1 as usize] = 0;
+            let loosened = SudokuBoard::from(grid).unwrap();
+            assert!(BacktrackingSolver::count_solutions(&loosened, 2) > 1, "{:?}={} is redundant", cell, value);
+        }
+
+        // A full grid reduces to a proper puzzle; a board with many solutions is left alone.
+        assert_eq!(BacktrackingSolver::count_solutions(&minimize(&solved), 2), 1);
+        let empty = SudokuBoard::from([[0; 9]; 9]).unwrap();
+        assert_eq!(minimize(&empty), empty);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_generate_has_unique_solution() {
+        let board = generate(32);
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 2), 1);
+        assert!(!board.is_complete());
+
+        // Every remaining number is a given, and the target was reached or nearly so.
+        assert!(board.filled_count() >= 32);
+        for r in 0..9 {
+            for c in 0..9 {
+                let mut scratch = board;
+                let is_given = scratch.try_place((r, c), 0).is_err();
+                assert_eq!(is_given, board.get((r, c)) != Some(0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_with_solution() {
+        let (puzzle, solution) = generate_with_solution(30, 2);
+        let (again, _) = generate_with_solution(30, 2);
+        assert_eq!(puzzle.get_board(), again.get_board());
+        assert_eq!(BacktrackingSolver::count_solutions(&puzzle, 2), 1);
+        assert!(puzzle.as_flat().iter().filter(|&&value| value != 0).count() >= 30);
+
+        // The solution is the puzzle's only one, and only the original clues are givens.
+        assert!(solution.is_complete());
+        assert!(SudokuBoard::is_valid_config(solution.get_board()));
+        let solved = crate::sudoku_solver::BacktrackingSolver::run(&puzzle).unwrap();
+        assert_eq!(solution.get_board(), solved.get_board());
+        assert_eq!(solution.givens_only().get_board(), puzzle.get_board());
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_generate_with_difficulty() {
+        use crate::sudoku_solver::{StepKind, Technique, solve_trace};
+
+        let easy = generate_with_difficulty(Difficulty::Easy, 2).unwrap();
+        assert_eq!(Some(easy), generate_with_difficulty(Difficulty::Easy, 2));
+        assert_eq!(rate_difficulty(&easy), Difficulty::Easy);
+
+        // Replaying the solve takes naked singles only: no guess and no backtrack.
+        let trace = solve_trace(&easy).unwrap();
+        assert!(trace.iter().all(|step| step.kind == StepKind::Place));
+        assert!(trace.iter().all(|step| step.technique == Some(Technique::NakedSingle)));
+        let empty = easy.get_board().iter().flatten().filter(|&&value| value == 0).count();
+        assert_eq!(trace.len(), empty);
+
+        // A medium puzzle needs hidden singles but no guessing; a hard one needs guessing.
+        let medium = generate_with_difficulty(Difficulty::Medium, 2).unwrap();
+        assert_eq!(rate_difficulty(&medium), Difficulty::Medium);
+        let hard = generate_with_difficulty(Difficulty::Hard, 2).unwrap();
+        assert_eq!(rate_difficulty(&hard), Difficulty::Hard);
+
+        assert_eq!(generate_with_difficulty(Difficulty::NotUnique, 2), None);
+    }
+}
@@ -0,0 +1,42 @@
+//! A Sudoku engine: boards, solvers and puzzle generation.
+//!
+//! Boards parse from 81-character lines, and `SudokuError` implements `Error`, so parse
+//! failures pass straight through `?`:
+//!
+//! ```
+//! use rust_sudoku::sudoku_board::SudokuBoard;
+//! use rust_sudoku::sudoku_solver::BacktrackingSolver;
+//! use std::error::Error;
+//!
+//! fn print_solution(line: &str) -> Result<(), Box<dyn Error>> {
+//!     let board: SudokuBoard = line.parse()?;
+//!     let solved = BacktrackingSolver::run(&board).ok_or("no solution")?;
+//!     println!("{:?}", solved.get_board());
+//!     Ok(())
+//! }
+//!
+//! print_solution("006040097040730010017092030600070080105060903020010006050980160090056070860020300").unwrap();
+//! assert!(print_solution("not a puzzle").is_err());
+//! ```
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "extra_solvers")]
+pub mod candidate_grid;
+pub mod error;
+// Sample puzzles for the tests and benchmarks, not part of the public API.
+#[doc(hidden)]
+pub mod fixtures;
+#[cfg(feature = "generator")]
+pub mod generator;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod sized_board;
+pub mod sudoku_board;
+pub mod sudoku_solver;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+mod rng;
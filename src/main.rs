@@ -1,8 +1,5 @@
-mod sudoku_board;
-mod sudoku_solver;
-
-use sudoku_board::SudokuBoard;
-use sudoku_solver::BacktrackingSolver;
+use rust_sudoku::sudoku_board::SudokuBoard;
+use rust_sudoku::sudoku_solver::BacktrackingSolver;
 
 fn main() {
     let config = [
@@ -17,8 +14,8 @@ fn main() {
         [8, 6, 0, 0, 2, 0, 3, 0, 0],
     ];
     
-    let mut board: SudokuBoard = SudokuBoard::from(config).expect("Build failed"); // will panic if config is invalid.
-    let mut solved_board = BacktrackingSolver::run(&board).unwrap();
+    let board: SudokuBoard = SudokuBoard::from(config).expect("Build failed"); // will panic if config is invalid.
+    let solved_board = BacktrackingSolver::run(&board).unwrap();
     board.print();
     println!("{}", "-".repeat(31));
     solved_board.print();
@@ -0,0 +1,285 @@
+// Render Module
+use crate::sudoku_board::SudokuBoard;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A 24-bit color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    // The color as HTML and SVG write it, e.g. `#1e64dc`.
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// The colors `to_colored_string`, `to_html` and `to_svg` give each kind of cell, so an
+/// app can theme the output instead of living with fixed colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorScheme {
+    /// Text of starting numbers.
+    pub given: Rgb,
+    /// Text of numbers the player placed.
+    pub placed: Rgb,
+    /// Text of numbers that repeat in their row, column or box, given or not.
+    pub conflict: Rgb,
+    /// Background of highlighted cells.
+    pub highlight: Rgb,
+}
+
+impl Default for ColorScheme {
+    /// Dark gray givens, blue entries, red clashes and a pale yellow highlight.
+    fn default() -> Self {
+        ColorScheme {
+            given: Rgb(64, 64, 64),
+            placed: Rgb(30, 100, 220),
+            conflict: Rgb(220, 40, 40),
+            highlight: Rgb(255, 240, 150),
+        }
+    }
+}
+
+// Colored output, for terminals and the web.
+impl SudokuBoard {
+    /// Renders the board in the layout of `print()`, coloring each number with the
+    /// scheme's ANSI (24-bit) colors and giving the cells in `highlight` the highlight
+    /// background. Empty cells show as uncolored 0s unless highlighted. Lines end with `\n`.
+    pub fn to_colored_string(&self, scheme: &ColorScheme, highlight: &[(u8, u8)]) -> String {
+        let separator = "-".repeat(31);
+        let mut out = format!("{}\n|{:^29}|\n{}\n", separator, "CURRENT BOARD STATE", separator);
+        let conflicts = self.conflicts();
+        for r in 0..9 {
+            out.push('|');
+            for c in 0..9 {
+                let (text, background) = self.cell_colors(scheme, &conflicts, highlight, (r, c));
+                let mut codes = Vec::new();
+                if let Some(Rgb(red, green, blue)) = text {
+                    codes.push(format!("38;2;{};{};{}", red, green, blue));
+                }
+                if let Some(Rgb(red, green, blue)) = background {
+                    codes.push(format!("48;2;{};{};{}", red, green, blue));
+                }
+                let value = self.get((r, c)).unwrap();
+                if codes.is_empty() {
+                    out.push_str(&format!(" {} ", value));
+                } else {
+                    out.push_str(&format!("\x1b[{}m {} \x1b[0m", codes.join(";"), value));
+                }
+                if c % 3 == 2 {
+                    out.push('|');
+                }
+            }
+            out.push('\n');
+            if r % 3 == 2 {
+                out.push_str(&separator);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Prints `to_colored_string` to stdout.
+    #[cfg(feature = "std")]
+    pub fn print_colored(&self, scheme: &ColorScheme, highlight: &[(u8, u8)]) {
+        print!("{}", self.to_colored_string(scheme, highlight));
+    }
+
+    /// Renders the board as an HTML `<table>` with inline styles: heavier borders around
+    /// the boxes, numbers in the scheme's colors, and the cells in `highlight` on the
+    /// highlight background. Empty cells are left blank.
+    pub fn to_html(&self, scheme: &ColorScheme, highlight: &[(u8, u8)]) -> String {
+        let conflicts = self.conflicts();
+        let mut out = String::from("<table style=\"border-collapse: collapse; border: 2px solid #000\">\n");
+        for r in 0..9 {
+            out.push_str("<tr>");
+            for c in 0..9 {
+                let mut style = String::from("width: 2em; height: 2em; text-align: center; border: 1px solid #999");
+                if c % 3 == 2 {
+                    style.push_str("; border-right: 2px solid #000");
+                }
+                if r % 3 == 2 {
+                    style.push_str("; border-bottom: 2px solid #000");
+                }
+                let (text, background) = self.cell_colors(scheme, &conflicts, highlight, (r, c));
+                if let Some(color) = background {
+                    style.push_str(&format!("; background: {}", color.hex()));
+                }
+                if let Some(color) = text {
+                    style.push_str(&format!("; color: {}", color.hex()));
+                }
+                let value = match self.get((r, c)).unwrap() {
+                    0 => String::new(),
+                    value => format!("{}", value),
+                };
+                out.push_str(&format!("<td style=\"{}\">{}</td>", style, value));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+        out
+    }
+
+    /// Renders the board as a standalone 360x360 SVG image: a white grid with heavier
+    /// lines around the boxes, numbers in the scheme's colors, and the cells in
+    /// `highlight` filled with the highlight color.
+    pub fn to_svg(&self, scheme: &ColorScheme, highlight: &[(u8, u8)]) -> String {
+        const CELL: usize = 40;
+        let size = 9 * CELL;
+        let conflicts = self.conflicts();
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">\n",
+            size
+        );
+        out.push_str(&format!("<rect width=\"{0}\" height=\"{0}\" fill=\"#ffffff\"/>\n", size));
+
+        let mut numbers = String::new();
+        for r in 0..9u8 {
+            for c in 0..9u8 {
+                let (x, y) = (c as usize * CELL, r as usize * CELL);
+                let (text, background) = self.cell_colors(scheme, &conflicts, highlight, (r, c));
+                if let Some(color) = background {
+                    out.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" fill=\"{3}\"/>\n",
+                        x,
+                        y,
+                        CELL,
+                        color.hex()
+                    ));
+                }
+                if let Some(color) = text {
+                    numbers.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" font-size=\"24\" text-anchor=\"middle\" \
+                         dominant-baseline=\"central\" fill=\"{}\">{}</text>\n",
+                        x + CELL / 2,
+                        y + CELL / 2,
+                        color.hex(),
+                        self.get((r, c)).unwrap()
+                    ));
+                }
+            }
+        }
+
+        for i in 0..=9 {
+            let at = i * CELL;
+            let width = if i % 3 == 0 { 3 } else { 1 };
+            out.push_str(&format!(
+                "<line x1=\"{0}\" y1=\"0\" x2=\"{0}\" y2=\"{1}\" stroke=\"#000\" stroke-width=\"{2}\"/>\n",
+                at, size, width
+            ));
+            out.push_str(&format!(
+                "<line x1=\"0\" y1=\"{0}\" x2=\"{1}\" y2=\"{0}\" stroke=\"#000\" stroke-width=\"{2}\"/>\n",
+                at, size, width
+            ));
+        }
+        out.push_str(&numbers);
+        out.push_str("</svg>\n");
+        out
+    }
+
+    // A cell's text color (None if it is empty) and background (None unless highlighted).
+    fn cell_colors(
+        &self,
+        scheme: &ColorScheme,
+        conflicts: &[(u8, u8)],
+        highlight: &[(u8, u8)],
+        cell: (u8, u8),
+    ) -> (Option<Rgb>, Option<Rgb>) {
+        let text = match self.get(cell) {
+            Some(0) | None => None,
+            Some(_) if conflicts.contains(&cell) => Some(scheme.conflict),
+            Some(_) if self.is_given(cell) == Some(true) => Some(scheme.given),
+            Some(_) => Some(scheme.placed),
+        };
+        (text, highlight.contains(&cell).then_some(scheme.highlight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The main.rs puzzle with a 3 placed in its empty top-left corner.
+    fn board() -> SudokuBoard {
+        let mut board = SudokuBoard::from([
+            [0, 0, 6, 0, 4, 0, 0, 9, 7],
+            [0, 4, 0, 7, 3, 0, 0, 1, 0],
+            [0, 1, 7, 0, 9, 2, 0, 3, 0],
+            [6, 0, 0, 0, 7, 0, 0, 8, 0],
+            [1, 0, 5, 0, 6, 0, 9, 0, 3],
+            [0, 2, 0, 0, 1, 0, 0, 0, 6],
+            [0, 5, 0, 9, 8, 0, 1, 6, 0],
+            [0, 9, 0, 0, 5, 6, 0, 7, 0],
+            [8, 6, 0, 0, 2, 0, 3, 0, 0],
+        ]).unwrap();
+        board.try_place((0, 0), 3).unwrap();
+        board
+    }
+
+    // The text with every ANSI escape sequence removed.
+    fn strip_ansi(text: &str) -> String {
+        let mut out = String::new();
+        let mut chars = text.chars();
+        while let Some(ch) = chars.next() {
+            if ch == '\x1b' {
+                chars.by_ref().find(|&ch| ch == 'm');
+            } else {
+                out.push(ch);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_colored_string_keeps_the_print_layout() {
+        let colored = board().to_colored_string(&ColorScheme::default(), &[(4, 4)]);
+        let plain = strip_ansi(&colored);
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines.len(), 15);
+        assert_eq!(lines[1], "|     CURRENT BOARD STATE     |");
+        for i in [0, 2, 6, 10, 14] {
+            assert_eq!(lines[i], "-".repeat(31));
+        }
+        assert_eq!(lines[3], "| 3  0  6 | 0  4  0 | 0  9  7 |");
+        assert_eq!(lines[13], "| 8  6  0 | 0  2  0 | 3  0  0 |");
+
+        assert!(colored.contains("\x1b[38;2;30;100;220m 3 \x1b[0m"));
+        assert!(colored.contains("\x1b[38;2;64;64;64m 6 \x1b[0m"));
+        assert!(colored.contains("\x1b[38;2;64;64;64;48;2;255;240;150m 6 \x1b[0m"));
+        assert!(colored.contains("| 0 "));
+    }
+
+    #[test]
+    fn test_colored_string_marks_conflicts() {
+        let mut board = board();
+        board.internal_place((0, 1), 3);
+        let colored = board.to_colored_string(&ColorScheme::default(), &[]);
+        assert_eq!(colored.matches("\x1b[38;2;220;40;40m 3 \x1b[0m").count(), 2);
+    }
+
+    #[test]
+    fn test_to_html_uses_the_scheme() {
+        let scheme = ColorScheme { placed: Rgb(1, 2, 3), highlight: Rgb(255, 0, 255), ..ColorScheme::default() };
+        let html = board().to_html(&scheme, &[(0, 1)]);
+        assert!(html.starts_with("<table"));
+        assert_eq!(html.matches("<tr>").count(), 9);
+        assert_eq!(html.matches("<td").count(), 81);
+        assert!(html.contains("; color: #010203\">3</td>"));
+        assert!(html.contains("; background: #ff00ff\"></td>"));
+        assert!(html.contains("; color: #404040\">6</td>"));
+    }
+
+    #[test]
+    fn test_to_svg_draws_every_number() {
+        let board = board();
+        let svg = board.to_svg(&ColorScheme::default(), &[(8, 8)]);
+        let filled = board.get_board().iter().flatten().filter(|&&num| num != 0).count();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<text").count(), filled);
+        assert_eq!(svg.matches("<line").count(), 20);
+        assert!(svg.contains("<rect x=\"320\" y=\"320\" width=\"40\" height=\"40\" fill=\"#fff096\"/>"));
+        assert!(svg.contains("fill=\"#1e64dc\">3</text>"));
+    }
+}
@@ -0,0 +1,302 @@
+// Sized Board Module
+use crate::error::SudokuError;
+use crate::sudoku_board::SudokuBoard;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A Sudoku board of any size, made of `B`x`B` boxes on a side of `B * B` cells: 4x4 for
+/// `B = 2`, the classic 9x9 for `B = 3`, 16x16 for `B = 4` and 25x25 for `B = 5`. Cells
+/// hold 1 to `B * B`, with 0 for empty.
+///
+/// This is a standalone type that shares no code with `SudokuBoard`: it can be built,
+/// played and solved with `BacktrackingSolver::run_sized`, but the other solvers, hints and
+/// the generator only take a `SudokuBoard`, which remains the fast, fixed-size type for
+/// 9x9 play.
+///
+/// `B` must be 2 to 5, so every value fits the 32-bit masks used for checking; anything
+/// else fails to compile:
+///
+/// ```compile_fail
+/// rust_sudoku::sized_board::SizedBoard::<6>::from(&[0; 1296]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SizedBoard<const B: usize> {
+    // Row-major cells and starting-number flags, SIDE * SIDE of each.
+    cells: Vec<u8>,
+    initial_mask: Vec<bool>,
+    // Bit n of row_masks[r] is set while n is in row r; likewise for columns and boxes.
+    row_masks: Vec<u32>,
+    col_masks: Vec<u32>,
+    box_masks: Vec<u32>,
+}
+
+/// A 4x4 beginner board with 2x2 boxes.
+pub type Board4 = SizedBoard<2>;
+/// A classic 9x9 board.
+pub type Board9 = SizedBoard<3>;
+/// A 16x16 board with 4x4 boxes.
+pub type Board16 = SizedBoard<4>;
+
+impl<const B: usize> SizedBoard<B> {
+    /// Cells on each side of the board, which is also the largest value a cell can hold.
+    pub const SIDE: usize = {
+        assert!(B >= 2 && B <= 5, "SizedBoard supports boxes of 2 to 5 cells a side");
+        B * B
+    };
+
+    /// Builds a board from `SIDE * SIDE` cells in row-major order. Every non-zero cell is a
+    /// starting number.
+    pub fn from(config: &[u8]) -> Result<Self, SudokuError> {
+        if config.len() != Self::SIDE * Self::SIDE {
+            return Err(SudokuError::InvalidLength);
+        }
+        if let Some(&value) = config.iter().find(|&&value| value as usize > Self::SIDE) {
+            return Err(SudokuError::InvalidValue(value));
+        }
+        if !Self::is_valid_config(config) {
+            return Err(SudokuError::InvalidConfig);
+        }
+
+        let mut board = SizedBoard {
+            cells: vec![0; config.len()],
+            initial_mask: config.iter().map(|&value| value != 0).collect(),
+            row_masks: vec![0; Self::SIDE],
+            col_masks: vec![0; Self::SIDE],
+            box_masks: vec![0; Self::SIDE],
+        };
+        for (index, &value) in config.iter().enumerate() {
+            board.internal_place(((index / Self::SIDE) as u8, (index % Self::SIDE) as u8), value);
+        }
+        Ok(board)
+    }
+
+    // Gets the value of a cell at the given coordinates.
+    pub fn get(&self, cell: (u8, u8)) -> Option<u8> {
+        self.index(cell).map(|index| self.cells[index])
+    }
+
+    /// Returns the cells in row-major order, so `(r, c)` is at index `r * SIDE + c`.
+    pub fn as_flat(&self) -> &[u8] {
+        &self.cells
+    }
+
+    /// Places `num`, or clears the cell with 0, with the same checks and errors as
+    /// `SudokuBoard::try_place`. A rejected move leaves the board unchanged.
+    pub fn try_place(&mut self, cell: (u8, u8), num: u8) -> Result<(), SudokuError> {
+        let index = self.index(cell).ok_or(SudokuError::OutOfBounds { row: cell.0, col: cell.1 })?;
+
+        if self.initial_mask[index] {
+            return Err(SudokuError::CannotModifyInitial);
+        }
+
+        if num == 0 {
+            self.internal_place(cell, 0);
+            return Ok(());
+        }
+
+        if self.cells[index] == num {
+            return Ok(());
+        }
+
+        if self.cells[index] != 0 {
+            return Err(SudokuError::CellFilled);
+        }
+
+        if num as usize > Self::SIDE {
+            return Err(SudokuError::InvalidValue(num));
+        }
+
+        if !self.is_placement_valid(cell, num) {
+            return Err(SudokuError::Conflict);
+        }
+
+        self.internal_place(cell, num);
+        Ok(())
+    }
+
+    /// Whether every cell is filled and no row, column or box repeats a value.
+    pub fn is_solved(&self) -> bool {
+        !self.cells.contains(&0) && Self::is_valid_config(&self.cells)
+    }
+
+    /// Checks `SIDE * SIDE` row-major cells for a value repeated within any row, column or
+    /// box. Empty cells are ignored; input of the wrong length is never valid.
+    pub fn is_valid_config(config: &[u8]) -> bool {
+        let side = Self::SIDE;
+        if config.len() != side * side {
+            return false;
+        }
+
+        // seen[unit] has bit n set once value n has appeared in that unit.
+        let mut rows = vec![0u32; side];
+        let mut cols = vec![0u32; side];
+        let mut boxes = vec![0u32; side];
+        for (index, &value) in config.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+            let (r, c) = (index / side, index % side);
+            let b = (r / B) * B + c / B;
+            let bit = 1u32 << value;
+            if (rows[r] | cols[c] | boxes[b]) & bit != 0 {
+                return false;
+            }
+            rows[r] |= bit;
+            cols[c] |= bit;
+            boxes[b] |= bit;
+        }
+        true
+    }
+
+    // Whether `num` appears nowhere else in the cell's row, column or box. Assumes `cell`
+    // is on the board.
+    pub(crate) fn is_placement_valid(&self, cell: (u8, u8), num: u8) -> bool {
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        let (box_r, box_c) = ((r / B) * B, (c / B) * B);
+        (0..Self::SIDE).all(|i| {
+            let peers = [(r, i), (i, c), (box_r + i / B, box_c + i % B)];
+            peers
+                .iter()
+                .all(|&(pr, pc)| (pr, pc) == (r, c) || self.cells[pr * Self::SIDE + pc] != num)
+        })
+    }
+
+    // Crate level helper -- writes a cell with no checks, for the solver's search. The
+    // masks stay right as long as the old value isn't repeated in one of the cell's units.
+    pub(crate) fn internal_place(&mut self, cell: (u8, u8), num: u8) {
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        let b = (r / B) * B + c / B;
+        let index = r * Self::SIDE + c;
+        let old = self.cells[index];
+        if old != 0 {
+            let clear = !(1u32 << old);
+            self.row_masks[r] &= clear;
+            self.col_masks[c] &= clear;
+            self.box_masks[b] &= clear;
+        }
+        if num != 0 {
+            let bit = 1u32 << num;
+            self.row_masks[r] |= bit;
+            self.col_masks[c] |= bit;
+            self.box_masks[b] |= bit;
+        }
+        self.cells[index] = num;
+    }
+
+    // The empty cell with the fewest candidates, ties going to the first in row-major
+    // order, paired with those candidates as a bitmask (bit n set if n fits). None if the
+    // board is full.
+    pub(crate) fn most_constrained_cell(&self) -> Option<((u8, u8), u32)> {
+        let all = (1u32 << (Self::SIDE + 1)) - 2;
+        let mut best: Option<((u8, u8), u32)> = None;
+        for index in (0..self.cells.len()).filter(|&index| self.cells[index] == 0) {
+            let (r, c) = (index / Self::SIDE, index % Self::SIDE);
+            let used = self.row_masks[r] | self.col_masks[c] | self.box_masks[(r / B) * B + c / B];
+            let candidates = all & !used;
+            if best.is_none_or(|(_, fewest)| candidates.count_ones() < fewest.count_ones()) {
+                best = Some(((r as u8, c as u8), candidates));
+                if candidates.count_ones() <= 1 {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    fn index(&self, cell: (u8, u8)) -> Option<usize> {
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        (r < Self::SIDE && c < Self::SIDE).then_some(r * Self::SIDE + c)
+    }
+}
+
+impl Board9 {
+    /// Copies a classic board, keeping its starting numbers. Fails with `InvalidConfig` if
+    /// the board repeats a number in a row, column or box.
+    pub fn from_classic(board: &SudokuBoard) -> Result<Self, SudokuError> {
+        let mut sized = Board9::from(board.as_flat())?;
+        sized.initial_mask = board.givens_only().as_flat().iter().map(|&value| value != 0).collect();
+        Ok(sized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 4x4 puzzle with a unique solution.
+    fn puzzle_4x4() -> Vec<u8> {
+        vec![
+            1, 0, 0, 0, //
+            0, 0, 3, 0, //
+            0, 4, 0, 0, //
+            0, 0, 0, 2,
+        ]
+    }
+
+    #[test]
+    fn test_from_checks_size_and_values() {
+        assert!(Board4::from(&puzzle_4x4()).is_ok());
+        assert_eq!(Board4::from(&[0; 15]), Err(SudokuError::InvalidLength));
+        assert_eq!(Board9::from(&puzzle_4x4()), Err(SudokuError::InvalidLength));
+
+        let mut config = puzzle_4x4();
+        config[1] = 5;
+        assert_eq!(Board4::from(&config), Err(SudokuError::InvalidValue(5)));
+
+        // Two 1s in the top-left 2x2 box.
+        config[1] = 0;
+        config[5] = 1;
+        assert_eq!(Board4::from(&config), Err(SudokuError::InvalidConfig));
+
+        assert!(Board16::from(&[0; 256]).is_ok());
+        assert_eq!(Board16::SIDE, 16);
+    }
+
+    #[test]
+    fn test_try_place() {
+        let mut board = Board4::from(&puzzle_4x4()).unwrap();
+        let before = board.clone();
+        assert_eq!(board.try_place((4, 0), 2), Err(SudokuError::OutOfBounds { row: 4, col: 0 }));
+        assert_eq!(board.try_place((0, 0), 2), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(board.try_place((0, 1), 5), Err(SudokuError::InvalidValue(5)));
+        assert_eq!(board.try_place((0, 1), 4), Err(SudokuError::Conflict));
+        assert_eq!(board, before);
+
+        assert_eq!(board.try_place((0, 1), 2), Ok(()));
+        assert_eq!(board.get((0, 1)), Some(2));
+        assert_eq!(board.try_place((0, 1), 3), Err(SudokuError::CellFilled));
+        assert_eq!(board.try_place((0, 1), 0), Ok(()));
+        assert_eq!(board, before);
+    }
+
+    #[test]
+    fn test_from_classic() {
+        let mut grid = [[0; 9]; 9];
+        grid[0] = [0, 0, 6, 0, 4, 0, 0, 9, 7];
+        let mut classic = SudokuBoard::from(grid).unwrap();
+        classic.try_place((0, 0), 1).unwrap();
+
+        let mut sized = Board9::from_classic(&classic).unwrap();
+        assert_eq!(sized.as_flat(), &classic.as_flat()[..]);
+        assert_eq!(sized.try_place((0, 2), 0), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(sized.try_place((0, 0), 0), Ok(()));
+
+        // A clash forced in past the classic board's checks is reported, not copied.
+        classic.internal_place((0, 1), 6);
+        assert_eq!(Board9::from_classic(&classic), Err(SudokuError::InvalidConfig));
+    }
+
+    #[test]
+    fn test_largest_box_size() {
+        // 25 is the largest value, and still fits the masks.
+        let mut config = vec![0; 625];
+        config[0] = 25;
+        config[1] = 25;
+        assert_eq!(SizedBoard::<5>::from(&config), Err(SudokuError::InvalidConfig));
+        config[1] = 24;
+        let mut board = SizedBoard::<5>::from(&config).unwrap();
+        assert_eq!(board.try_place((0, 2), 25), Err(SudokuError::Conflict));
+        assert_eq!(board.try_place((1, 5), 25), Ok(()));
+        assert!(!board.is_solved());
+    }
+}
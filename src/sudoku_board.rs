@@ -1,18 +1,86 @@
 // Sudoku Board Module
-use std::collections::HashSet;
+use crate::error::SudokuError;
+use crate::sudoku_solver::{BacktrackingSolver, Solution, SolveOutcome};
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "render")]
+use alloc::{format, vec};
+#[cfg(feature = "render")]
+use core::fmt;
+use core::str::FromStr;
 
-#[derive(Clone, Copy)]
+/// A 9x9 board with its starting numbers. Two boards are equal when they hold the same
+/// numbers and the same cells are givens; a placed 5 and a given 5 are not interchangeable.
+/// Hashing follows the same rule, so boards can be deduplicated in a `HashSet`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SudokuBoard {
     board: [[u8; 9]; 9],
     initial_mask: [[bool; 9]; 9],
+    // Bit n is set when number n is present in that row, column or box (boxes in reading
+    // order). Kept in sync by internal_place so placement checks are O(1).
+    row_masks: [u16; 9],
+    col_masks: [u16; 9],
+    box_masks: [u16; 9],
+}
+
+/// Controls how leniently `SudokuBoard::from_str_opts` reads a puzzle string.
+///
+/// The default is strict: exactly 81 characters of `0`-`9`, with `0` for empty cells
+/// and no whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Skip whitespace (including newlines) anywhere in the input.
+    pub skip_whitespace: bool,
+    /// Skip `|`, `+` and `-` anywhere in the input, so box borders drawn around the grid
+    /// are ignored.
+    pub skip_separators: bool,
+    /// Accept `.` as an empty cell in addition to `0`.
+    pub allow_dot: bool,
+    /// Reject input with more than 81 cells. When false, anything after the 81st cell is ignored.
+    pub strict_length: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            skip_whitespace: false,
+            skip_separators: false,
+            allow_dot: false,
+            strict_length: true,
+        }
+    }
+}
+
+/// The three kinds of nine-cell unit on a Sudoku board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitKind {
+    Row,
+    Column,
+    Box,
+}
+
+/// A cell paired with a value for that cell.
+pub type Placement = ((u8, u8), u8);
+
+/// Partial-credit score of a player's entries against a known solution.
+/// Starting numbers are not counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grade {
+    pub correct: usize,
+    pub incorrect: usize,
+    pub empty: usize,
 }
 
 impl SudokuBoard {
     // Class Constructor
     // Assume config always exists for now.
-    pub fn from(config: [[u8; 9]; 9]) -> Result<Self, &'static str> {
+    pub fn from(config: [[u8; 9]; 9]) -> Result<Self, SudokuError> {
+        let out_of_range = config.as_flattened().iter().find(|&&value| value != 0 && !Self::is_valid_symbol(value));
+        if let Some(&value) = out_of_range {
+            return Err(SudokuError::InvalidValue(value));
+        }
         if !Self::is_valid_config(&config) {
-            return Err("Error: Invalid config used in SudokuBoard::from().");
+            return Err(SudokuError::InvalidConfig);
         }
 
         let mut initial_mask = [[false; 9]; 9];
@@ -24,12 +92,126 @@ impl SudokuBoard {
             }
         }
 
-        Ok(SudokuBoard {
+        let mut board = SudokuBoard {
             board: config,
             initial_mask,
-        })
+            row_masks: [0; 9],
+            col_masks: [0; 9],
+            box_masks: [0; 9],
+        };
+        board.recompute_masks();
+        Ok(board)
     }
-    
+
+    /// Restores a board with an explicit choice of starting numbers, e.g. a game saved
+    /// mid-solve: cells marked in `givens` are locked and every other filled cell stays a
+    /// player entry. The grid is validated like `from`, and a given on an empty cell is an
+    /// `InvalidConfig` error.
+    pub fn from_state(grid: [[u8; 9]; 9], givens: [[bool; 9]; 9]) -> Result<Self, SudokuError> {
+        let mut board = Self::from(grid)?;
+        let given_on_empty = (0..81).any(|i| givens[i / 9][i % 9] && grid[i / 9][i % 9] == 0);
+        if given_on_empty {
+            return Err(SudokuError::InvalidConfig);
+        }
+        board.initial_mask = givens;
+        Ok(board)
+    }
+
+    /// A blank board with no starting numbers, so every cell is editable.
+    pub fn empty() -> SudokuBoard {
+        SudokuBoard {
+            board: [[0; 9]; 9],
+            initial_mask: [[false; 9]; 9],
+            row_masks: [0; 9],
+            col_masks: [0; 9],
+            box_masks: [0; 9],
+        }
+    }
+
+    /// Builds a board from exactly 81 cells in row-major order, e.g. a `Vec<u8>` read from
+    /// storage. Every non-zero cell is a starting number, as with `from`.
+    pub fn from_flat(cells: &[u8]) -> Result<Self, SudokuError> {
+        if cells.len() != 81 {
+            return Err(SudokuError::InvalidLength);
+        }
+        let mut config = [[0; 9]; 9];
+        config.as_flattened_mut().copy_from_slice(cells);
+        Self::from(config)
+    }
+
+    /// Builds a board from a row-major string of 81 cells, as controlled by `options`.
+    pub fn from_str_opts(s: &str, options: &ParseOptions) -> Result<Self, SudokuError> {
+        let mut config = [[0; 9]; 9];
+        let mut count = 0;
+        for ch in s.chars() {
+            if options.skip_whitespace && ch.is_whitespace() {
+                continue;
+            }
+            if options.skip_separators && matches!(ch, '|' | '+' | '-') {
+                continue;
+            }
+            if count == 81 {
+                if options.strict_length {
+                    return Err(SudokuError::InvalidLength);
+                }
+                break;
+            }
+            config[count / 9][count % 9] = match ch {
+                '0'..='9' => ch as u8 - b'0',
+                '.' if options.allow_dot => 0,
+                _ => return Err(SudokuError::InvalidCharacter(ch)),
+            };
+            count += 1;
+        }
+        if count < 81 {
+            return Err(SudokuError::InvalidLength);
+        }
+        Self::from(config).map_err(|_| SudokuError::InvalidConfig)
+    }
+
+    /// Builds a board from a visual grid of nine lines with nine cells each, as found in
+    /// puzzle books and websites. Cells are `1-9`, with `0`, `.` or `_` for empty; spaces
+    /// and `|` between cells are ignored, as are blank lines and divider lines made of `-`,
+    /// `+` and `|`. Errors report the line and column that could not be read.
+    pub fn from_grid_str(s: &str) -> Result<Self, SudokuError> {
+        let mut config = [[0; 9]; 9];
+        let mut rows = 0;
+        for (line_index, line) in s.lines().enumerate() {
+            let is_divider = line.chars().all(|ch| ch.is_whitespace() || matches!(ch, '-' | '+' | '|'));
+            if is_divider {
+                continue;
+            }
+
+            let line_number = line_index + 1;
+            let mut cells = 0;
+            for (col_index, ch) in line.chars().enumerate() {
+                if ch.is_whitespace() || ch == '|' {
+                    continue;
+                }
+                let error = SudokuError::InvalidGrid { line: line_number, column: col_index + 1 };
+                // Too many rows, or too many cells on this row.
+                if rows == 9 || cells == 9 {
+                    return Err(error);
+                }
+                config[rows][cells] = match ch {
+                    '1'..='9' => ch as u8 - b'0',
+                    '0' | '.' | '_' => 0,
+                    _ => return Err(error),
+                };
+                cells += 1;
+            }
+            if cells < 9 {
+                return Err(SudokuError::InvalidGrid { line: line_number, column: line.chars().count() + 1 });
+            }
+            rows += 1;
+        }
+
+        if rows < 9 {
+            return Err(SudokuError::InvalidLength);
+        }
+        Self::from(config)
+    }
+
     // Gets the value of a cell at the given coordinates.
     pub fn get(&self, cell: (u8, u8)) -> Option<u8> {
         // Validates that the cell is on the board and returns value if it is
@@ -39,264 +221,1885 @@ impl SudokuBoard {
             .copied()
     }
 
+    /// Gets the value at row `r`, column `c` without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure `r < 9` and `c < 9`. Out-of-range indices are undefined
+    /// behavior. Use `get` unless the indices have already been validated.
+    pub unsafe fn get_unchecked(&self, r: usize, c: usize) -> u8 {
+        // SAFETY: the caller guarantees both indices are within the 9x9 grid.
+        unsafe { *self.board.get_unchecked(r).get_unchecked(c) }
+    }
+
     // Returns a reference to the board.
     pub fn get_board(&self) -> &[[u8; 9]; 9] {
-        return &self.board;
+        &self.board
+    }
+
+    /// Which cells are starting numbers, laid out like `get_board`. Together they are
+    /// everything `from_state` needs to rebuild the board.
+    pub fn get_givens(&self) -> &[[bool; 9]; 9] {
+        &self.initial_mask
+    }
+
+    /// Serializes the board as 81 characters in row-major order, with `0` for empty cells.
+    /// The result parses back into a board with the same cells.
+    pub fn to_line(&self) -> String {
+        self.to_line_with('0')
+    }
+
+    /// Like `to_line`, but writes `empty` for empty cells (e.g. `'.'`).
+    pub fn to_line_with(&self, empty: char) -> String {
+        self.board
+            .iter()
+            .flatten()
+            .map(|&value| if value == 0 { empty } else { (b'0' + value) as char })
+            .collect()
+    }
+
+    /// Iterates over all 81 cells in row-major order as `((row, col), value)`, without allocating.
+    pub fn iter_cells(&self) -> impl Iterator<Item = ((u8, u8), u8)> {
+        self.as_flat()
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| (((index / 9) as u8, (index % 9) as u8), value))
+    }
+
+    /// Iterates over the coordinates of the empty cells in row-major order.
+    pub fn iter_empty(&self) -> impl Iterator<Item = (u8, u8)> {
+        self.iter_cells().filter(|&(_, value)| value == 0).map(|(cell, _)| cell)
+    }
+
+    /// Returns the board as a flat, zero-copy view of its 81 cells in row-major order,
+    /// so the value at `(r, c)` is at index `r * 9 + c`.
+    pub fn as_flat(&self) -> &[u8; 81] {
+        // A [[u8; 9]; 9] is laid out contiguously, so the flattened slice is always 81 long.
+        self.board.as_flattened().try_into().unwrap()
+    }
+
+    /// The recommended way to change a cell: places `num`, or clears the cell with 0, only
+    /// if the move passes every check, and otherwise returns the reason and leaves the board
+    /// exactly as it was. Same checks and errors as `try_place`.
+    pub fn try_set(&mut self, cell: (u8, u8), num: u8) -> Result<(), SudokuError> {
+        self.try_place(cell, num)
+    }
+
+    /// Empties a cell the player filled. Clearing an already empty cell is a no-op; starting
+    /// numbers and cells off the board are errors.
+    pub fn clear(&mut self, cell: (u8, u8)) -> Result<(), SudokuError> {
+        self.try_place(cell, 0)
+    }
+
+    /// Restarts the puzzle: clears every player entry, keeping the starting numbers.
+    pub fn reset(&mut self) {
+        *self = self.givens_only();
     }
 
-    pub fn try_place(&mut self, cell: (u8, u8), num: u8) -> Result<(), &'static str> {
+    pub fn try_place(&mut self, cell: (u8, u8), num: u8) -> Result<(), SudokuError> {
         let (r, c) = (cell.0 as usize, cell.1 as usize);
 
         // Cell must be valid.
         if r >= 9 || c >= 9 {
-            return Err("Error: Cell is out of bounds.");
+            return Err(SudokuError::OutOfBounds { row: cell.0, col: cell.1 });
         }
 
         // Modifying starting number is invalid.
         if self.initial_mask[r][c] {
-            return Err("Error: Cannot modify a starting number.");
+            return Err(SudokuError::CannotModifyInitial);
         }
 
         // Clearing a cell is always valid.
         if num == 0 {
-            self.board[r][c] = 0;
+            self.internal_place(cell, 0);
+            return Ok(());
+        }
+
+        // Re-placing the value a cell already holds is a no-op.
+        if self.board[r][c] == num {
             return Ok(());
         }
 
         // Writing over a filled cell is invalid.
         if self.board[r][c] != 0 {
-            return Err("Error: Cannot overwrite filled cell. Try clearing first.")
+            return Err(SudokuError::CellFilled);
         }
 
         // Number must be valid (1-9).
-        if !(1..=9).contains(&num) {
-            return Err("Error: Number must be between 1 and 9.");
+        if !Self::is_valid_symbol(num) {
+            return Err(SudokuError::InvalidValue(num));
         }
 
         // Check for Sudoku rule conflicts.
         if !self.is_placement_valid(cell, num) {
-            return Err("Error: Move conflicts with another number.");
+            return Err(SudokuError::Conflict);
         }
 
         // If all checks pass, make the move.
-        self.board[r][c] = num;
+        self.internal_place(cell, num);
         Ok(())
     }
 
-    pub fn print(&self) {
-        // Print the Sudoku Board in a human readable
-        println!("{}", "-".repeat(31));
-        println!("|     CURRENT BOARD STATE      |");
-        println!("{}", "-".repeat(31));
-        for (row_index, row) in self.board.iter().enumerate() {
-            print!("|");
-            for (col_index, &element) in row.iter().enumerate() {
-                print!(" {} ", element);
-                if (col_index + 1) % 3 == 0 {
-                    print!("|")
+    /// Swaps two columns of the same vertical stack, moving both values and starting numbers.
+    /// Such a swap always keeps a valid board valid; columns in different stacks are rejected.
+    pub fn swap_columns(&mut self, a: u8, b: u8) -> Result<(), SudokuError> {
+        if a >= 9 {
+            return Err(SudokuError::InvalidIndex(a));
+        }
+        if b >= 9 {
+            return Err(SudokuError::InvalidIndex(b));
+        }
+        if a / 3 != b / 3 {
+            return Err(SudokuError::DifferentStacks);
+        }
+
+        let (a, b) = (a as usize, b as usize);
+        for r in 0..9 {
+            self.board[r].swap(a, b);
+            self.initial_mask[r].swap(a, b);
+        }
+        self.recompute_masks();
+        Ok(())
+    }
+
+    /// Returns the board turned a quarter turn clockwise, so row 0 becomes column 8.
+    pub fn rotate90(&self) -> SudokuBoard {
+        self.remapped(|r, c| (8 - c, r))
+    }
+
+    /// Returns the board mirrored across its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> SudokuBoard {
+        self.remapped(|r, c| (c, r))
+    }
+
+    /// Returns the board mirrored top to bottom, so row 0 becomes row 8.
+    pub fn mirror_horizontal(&self) -> SudokuBoard {
+        self.remapped(|r, c| (8 - r, c))
+    }
+
+    /// Returns a standard representative of this board among its equivalent forms, so two
+    /// puzzles that are the same up to those changes give equal results. The forms considered
+    /// are a documented subset of the full Sudoku symmetry group: the eight rotations and
+    /// reflections, any order of the three bands (rows 0-2, 3-5, 6-8) and of the three
+    /// stacks, and any renaming of the digits. Swapping rows within a band or columns within
+    /// a stack is not considered. The representative is the form with the smallest
+    /// `to_line()`; starting numbers move with their cells.
+    pub fn canonical(&self) -> SudokuBoard {
+        const ORDERS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+        let mut best: Option<(String, SudokuBoard)> = None;
+        let mut orientation = *self;
+        for turn in 0..8 {
+            // Four quarter turns bring the board back, so continue with its mirror image.
+            if turn == 4 {
+                orientation = orientation.transpose();
+            }
+            for bands in ORDERS {
+                for stacks in ORDERS {
+                    let arranged = orientation.remapped(|r, c| (bands[r / 3] * 3 + r % 3, stacks[c / 3] * 3 + c % 3));
+                    let form = arranged.relabel(arranged.first_seen_labels()).expect("labels are a permutation");
+                    let line = form.to_line();
+                    if best.as_ref().is_none_or(|(smallest, _)| line < *smallest) {
+                        best = Some((line, form));
+                    }
                 }
             }
-            println!();
-            if (row_index + 1) % 3 == 0 {
-                println!("{}", "-".repeat(31));
+            orientation = orientation.rotate90();
+        }
+        best.expect("at least one form is considered").1
+    }
+
+    // The digit mapping that numbers digits 1, 2, 3, ... in the order they first appear in
+    // row-major order, which gives the smallest `to_line()` of any renaming.
+    fn first_seen_labels(&self) -> [u8; 9] {
+        let mut mapping = [0; 9];
+        let mut next = 1;
+        let unused = (1..=9).filter(|num| !self.as_flat().contains(num));
+        for num in self.as_flat().iter().copied().filter(|&num| num != 0).chain(unused) {
+            if mapping[num as usize - 1] == 0 {
+                mapping[num as usize - 1] = next;
+                next += 1;
             }
         }
+        mapping
     }
 
-    // Crate level helper to check if placing a number would violate Sudoku rules.
-    // Assumes cell and num are valid.
-    pub(crate) fn is_placement_valid(&self, cell: (u8, u8), num: u8) -> bool {
-        let (r, c) = (cell.0 as usize, cell.1 as usize);
+    // A board whose cell (r, c) takes the value and given flag of cell `source(r, c)` here.
+    // Only for rearrangements that keep every row, column and box together.
+    fn remapped(&self, source: impl Fn(usize, usize) -> (usize, usize)) -> SudokuBoard {
+        let mut board = *self;
+        for r in 0..9 {
+            for c in 0..9 {
+                let (sr, sc) = source(r, c);
+                board.board[r][c] = self.board[sr][sc];
+                board.initial_mask[r][c] = self.initial_mask[sr][sc];
+            }
+        }
+        board.recompute_masks();
+        board
+    }
 
-        // Check for duplicates in the same row and column, ignoring the cell itself.
-        for i in 0..9 {
-            if self.board[r][i] == num && i != c { return false; }
-            if self.board[i][c] == num && i != r { return false; }
+    /// Renames every digit in place, with digit `d` becoming `mapping[d - 1]`. Empty cells
+    /// and starting numbers stay where they are. Fails without changes unless `mapping` is a
+    /// permutation of 1-9.
+    pub fn relabel_inplace(&mut self, mapping: [u8; 9]) -> Result<(), SudokuError> {
+        let mut seen = [false; 10];
+        for &num in &mapping {
+            if !Self::is_valid_symbol(num) || seen[num as usize] {
+                return Err(SudokuError::InvalidMapping);
+            }
+            seen[num as usize] = true;
         }
 
-        // Check for duplicates in the 3x3 box, ignoring the cell itself.
-        let box_start_row = r - r % 3;
-        let box_start_col = c - c % 3;
-        for i in 0..3 {
-            for j in 0..3 {
-                let current_row = box_start_row + i;
-                let current_col = box_start_col + j;
-                if self.board[current_row][current_col] == num && (current_row != r || current_col != c) {
-                    return false;
-                }
+        for value in self.board.iter_mut().flatten() {
+            if *value != 0 {
+                *value = mapping[*value as usize - 1];
             }
         }
-        true
+        self.recompute_masks();
+        Ok(())
     }
 
-    // Crate level function -- Assumes cell & num are valid.
-    pub(crate) fn internal_place(&mut self, cell: (u8, u8), num: u8) {
-        self.board[cell.0 as usize][cell.1 as usize] = num;
+    /// Like `relabel_inplace`, but returns the renamed copy and leaves this board as it is.
+    pub fn relabel(&self, mapping: [u8; 9]) -> Result<SudokuBoard, SudokuError> {
+        let mut board = *self;
+        board.relabel_inplace(mapping)?;
+        Ok(board)
     }
-    
-    // Crate level function -- 
-    pub fn is_valid_config(config: &[[u8; 9]; 9]) -> bool {
-        // Check rows and columns for duplicates
-        for i in 0..9 {
-            let mut row_seen = HashSet::with_capacity(9);
-            let mut col_seen = HashSet::with_capacity(9);
-            for j in 0..9 {
-                // Check the current row
-                if config[i][j] != 0 {
-                    // If the number is already in the set, it's a duplicate.
-                    if !row_seen.insert(config[i][j]) {
-                        return false;
+
+    /// Places `num` like `try_place` and locks it as a starting number. A cell that
+    /// already holds `num` is promoted in place, letting authors lock entries as clues.
+    pub fn set_given(&mut self, cell: (u8, u8), num: u8) -> Result<(), SudokuError> {
+        // Clearing is allowed by try_place, but an empty cell can't be a given.
+        if !Self::is_valid_symbol(num) {
+            return Err(SudokuError::InvalidValue(num));
+        }
+
+        if self.get(cell) != Some(num) {
+            self.try_place(cell, num)?;
+        }
+        self.initial_mask[cell.0 as usize][cell.1 as usize] = true;
+        Ok(())
+    }
+
+    /// Locks every filled cell as a starting number, turning a hand-entered grid into the
+    /// puzzle to solve. Empty cells stay editable.
+    pub fn freeze(&mut self) {
+        for (mask, value) in self.initial_mask.iter_mut().flatten().zip(self.board.iter().flatten()) {
+            *mask |= *value != 0;
+        }
+    }
+
+    /// Places `num` like `try_place` and returns `(peer, num)` for every empty peer that had
+    /// `num` as a candidate before the move, i.e. exactly the pencil marks to erase.
+    /// On error the board is unchanged.
+    pub fn try_place_tracked(&mut self, cell: (u8, u8), num: u8) -> Result<Vec<Placement>, SudokuError> {
+        // Let try_place vet the move first, then read the candidates as they were before it.
+        let before = *self;
+        self.try_place(cell, num)?;
+
+        let mut eliminated = Vec::new();
+        if num != 0 {
+            for r in 0..9 {
+                for c in 0..9 {
+                    if Self::is_peer(cell, (r, c))
+                        && before.board[r as usize][c as usize] == 0
+                        && before.is_placement_valid((r, c), num)
+                    {
+                        eliminated.push(((r, c), num));
                     }
                 }
-                // Check the current column
-                if config[j][i] != 0 {
-                    if !col_seen.insert(config[j][i]) {
-                        return false;
-                    }
+            }
+        }
+        Ok(eliminated)
+    }
+
+    /// Places `num` like `try_place`, then returns each empty peer that the placement
+    /// reduced to a single candidate, paired with that candidate. The forced values are
+    /// reported but not applied.
+    pub fn place_and_forced(&mut self, cell: (u8, u8), num: u8) -> Result<Vec<Placement>, SudokuError> {
+        // Only empty peers of the cell can lose a candidate, so record their counts first.
+        let mut before = [[0u8; 9]; 9];
+        for r in 0..9 {
+            for c in 0..9 {
+                if self.board[r as usize][c as usize] == 0 && Self::is_peer(cell, (r, c)) {
+                    before[r as usize][c as usize] = self.candidate_count((r, c)).unwrap();
                 }
             }
         }
 
-        // Check 3x3 boxes for duplicates
-        for box_row in (0..9).step_by(3) {
-            for box_col in (0..9).step_by(3) {
-                let mut box_seen = HashSet::with_capacity(9);
-                for r in box_row..box_row + 3 {
-                    for c in box_col..box_col + 3 {
-                        if config[r][c] != 0 {
-                            if !box_seen.insert(config[r][c]) {
-                                return false;
-                            }
-                        }
-                    }
+        self.try_place(cell, num)?;
+
+        let mut forced = Vec::new();
+        for r in 0..9 {
+            for c in 0..9 {
+                if before[r as usize][c as usize] > 1 && self.candidate_count((r, c)).unwrap() == 1 {
+                    forced.push(((r, c), self.candidates((r, c))[0]));
                 }
             }
         }
-        // If no duplicates were found, the configuration is valid.
-        true
+        Ok(forced)
     }
-}
 
+    /// Returns the 20 cells that share a row, column or box with `cell`, not counting `cell`
+    /// itself, in row-major order. Returns None if `cell` is off the board.
+    pub fn peers(cell: (u8, u8)) -> Option<Vec<(u8, u8)>> {
+        if cell.0 >= 9 || cell.1 >= 9 {
+            return None;
+        }
+        let all = (0..9).flat_map(|r| (0..9).map(move |c| (r, c)));
+        Some(all.filter(|&other| Self::is_peer(cell, other)).collect())
+    }
 
+    // Whether two distinct cells share a row, column or box.
+    pub(crate) fn is_peer(a: (u8, u8), b: (u8, u8)) -> bool {
+        a != b && (a.0 == b.0 || a.1 == b.1 || (a.0 / 3 == b.0 / 3 && a.1 / 3 == b.1 / 3))
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Counts how many times each digit appears, with index 0 holding the count of 1s.
+    /// Empty cells are not counted, so a solved board gives `[9; 9]`.
+    pub fn digit_counts(&self) -> [u8; 9] {
+        let mut counts = [0; 9];
+        for &value in self.board.iter().flatten() {
+            if value != 0 {
+                counts[value as usize - 1] += 1;
+            }
+        }
+        counts
+    }
 
-    // A valid Sudoku configuration for use in tests.
-    fn valid_config() -> [[u8; 9]; 9] {
-        [
-            [0, 0, 6, 0, 4, 0, 0, 9, 7],
-            [0, 4, 0, 7, 3, 0, 0, 1, 0],
-            [0, 1, 7, 0, 9, 2, 0, 3, 0],
-            [6, 0, 0, 0, 7, 0, 0, 8, 0],
-            [1, 0, 5, 0, 6, 0, 9, 0, 3],
-            [0, 2, 0, 0, 1, 0, 0, 0, 6],
-            [0, 5, 0, 9, 8, 0, 1, 6, 0],
-            [0, 9, 0, 0, 5, 6, 0, 7, 0],
-            [8, 6, 0, 0, 2, 0, 3, 0, 0],
-        ]
+    /// Returns true if no cell is empty. Unlike `is_solved` this does not re-check the
+    /// Sudoku rules, so it is only a win check for boards known to be consistent, such as
+    /// those changed solely via `try_place`.
+    pub fn is_complete(&self) -> bool {
+        self.board.iter().flatten().all(|&value| value != 0)
     }
 
-    // An invalid configuration with a duplicate number in the second row.
-    fn invalid_row_config() -> [[u8; 9]; 9] {
-        [
-            [0, 0, 0, 2, 6, 0, 7, 0, 1],
-            [6, 8, 0, 0, 7, 0, 0, 8, 0], // duplicate 8
-            [1, 9, 0, 0, 0, 4, 5, 0, 0],
-            [8, 2, 0, 1, 0, 0, 0, 4, 0],
-            [0, 0, 4, 6, 0, 2, 9, 0, 0],
-            [0, 5, 0, 0, 0, 3, 0, 2, 8],
-            [0, 0, 9, 3, 0, 0, 0, 7, 4],
-            [0, 4, 0, 0, 5, 0, 0, 3, 6],
-            [7, 0, 3, 0, 1, 8, 0, 0, 0]
-        ]
+    /// Fills every empty cell with a solution, keeping all filled cells. Fails with
+    /// `InvalidConfig` if the board already breaks a rule and `Unsolvable` if it can't be
+    /// completed; either way the board is left unchanged.
+    pub fn solve(&mut self) -> Result<(), SudokuError> {
+        match BacktrackingSolver::run_outcome(self) {
+            SolveOutcome::Solved(solved) => {
+                *self = solved;
+                Ok(())
+            }
+            SolveOutcome::Unsolvable => Err(SudokuError::Unsolvable),
+            SolveOutcome::Aborted(_) => Err(SudokuError::InvalidConfig),
+        }
     }
 
-    // An invalid configuration with a duplicate number in the second column.
-    fn invalid_col_config() -> [[u8; 9]; 9] {
-        [
-            [0, 9, 0, 2, 6, 0, 7, 0, 1],
-            [6, 0, 0, 0, 7, 0, 0, 8, 0],
-            [1, 9, 0, 0, 0, 4, 5, 0, 0], // duplicate 9
-            [8, 2, 0, 1, 0, 0, 0, 4, 0],
-            [0, 0, 4, 6, 0, 2, 9, 0, 0],
-            [0, 5, 0, 0, 0, 3, 0, 2, 8],
-            [0, 0, 9, 3, 0, 0, 0, 7, 4],
-            [0, 4, 0, 0, 5, 0, 0, 3, 6],
-            [7, 0, 3, 0, 1, 8, 0, 0, 0]
-        ]
+    /// Returns true if no row, column or box repeats a number among the filled cells, given
+    /// or entered. Unlike `is_solved`, empty cells are fine.
+    pub fn is_consistent(&self) -> bool {
+        Self::is_valid_config(&self.board)
     }
 
-    // An invalid configuration with a duplicate number in the top-right(1, 3) box.
-    fn invalid_box_config() -> [[u8; 9]; 9] {
-        [
-            [0, 0, 0, 2, 6, 0, 7, 0, 1],
-            [6, 0, 0, 0, 7, 0, 0, 8, 0],
-            [1, 9, 0, 0, 0, 4, 5, 0, 7], // duplicate 7
-            [8, 2, 0, 1, 0, 0, 0, 4, 0],
-            [0, 0, 4, 6, 0, 2, 9, 0, 0],
-            [0, 5, 0, 0, 0, 3, 0, 2, 8],
-            [0, 0, 9, 3, 0, 0, 0, 7, 4],
-            [0, 4, 0, 0, 5, 0, 0, 3, 6],
-            [7, 0, 3, 0, 1, 8, 0, 0, 0]
-        ]
+    /// Returns whether the cell is a starting number, or None if it is off the board.
+    pub fn is_given(&self, cell: (u8, u8)) -> Option<bool> {
+        self.initial_mask.get(cell.0 as usize)?.get(cell.1 as usize).copied()
     }
 
-    #[test]
-    fn test_init_from_valid_config() {
-        // The from() function should succeed with a valid configuration.
-        assert!(SudokuBoard::from(valid_config()).is_ok());
+    /// Returns the number of starting numbers.
+    pub fn given_count(&self) -> usize {
+        self.initial_mask.iter().flatten().filter(|&&given| given).count()
     }
 
-    #[test]
-    fn test_init_from_invalid_configs() {
-        // The from() function should return an error for an invalid configuration.
-        assert!(SudokuBoard::from(invalid_row_config()).is_err());
-        assert!(SudokuBoard::from(invalid_col_config()).is_err());
-        assert!(SudokuBoard::from(invalid_box_config()).is_err());
+    /// Returns the number of empty cells.
+    pub fn empty_count(&self) -> usize {
+        self.as_flat().iter().filter(|&&value| value == 0).count()
     }
 
-    #[test]
-    fn test_get_value() {
-        // Test that get() retrieves the correct value from the board.
-        let board = SudokuBoard::from(valid_config()).unwrap();
-        assert_eq!(board.get((0, 2)), Some(6)); // Should be 6
-        assert_eq!(board.get((0, 0)), Some(0)); // Should be 0 
-        assert_eq!(board.get((9, 9)), None);   // Out of bounds
+    /// Returns the number of filled cells, starting numbers and player entries alike.
+    pub fn filled_count(&self) -> usize {
+        81 - self.empty_count()
     }
 
-    #[test]
-    fn test_try_place_scenarios() {
-        let mut board = SudokuBoard::from(valid_config()).unwrap();
+    /// Returns true if every cell is filled and no row, column or box repeats a number.
+    pub fn is_solved(&self) -> bool {
+        self.is_complete() && self.is_consistent()
+    }
 
-        // --- Success Cases ---
-        // Placing a valid number on an empty cell.
-        assert!(board.try_place((0, 0), 2).is_ok());
-        assert_eq!(board.get((0, 0)), Some(2));
-        
-        // Clearing a placed number.
-        assert!(board.try_place((0, 0), 0).is_ok());
-        assert_eq!(board.get((0, 0)), Some(0));
+    /// Grades the player's entries against `solution`, skipping starting numbers.
+    pub fn grade_against(&self, solution: &Solution) -> Grade {
+        let solution = solution.as_board();
+        let mut grade = Grade { correct: 0, incorrect: 0, empty: 0 };
+        for r in 0..9 {
+            for c in 0..9 {
+                if self.initial_mask[r][c] {
+                    continue;
+                }
+                match self.board[r][c] {
+                    0 => grade.empty += 1,
+                    num if num == solution.board[r][c] => grade.correct += 1,
+                    _ => grade.incorrect += 1,
+                }
+            }
+        }
+        grade
+    }
 
-        // --- Failure Cases ---
-        // Overwriting a placed number with non-0 number
-        board.try_place((0, 0), 3);
-        assert!(!board.try_place((0, 0), 4).is_ok());
+    /// Returns true if the puzzle has fewer than 17 starting numbers, the proven minimum
+    /// for a Sudoku with a unique solution. This is a necessary condition only: a puzzle
+    /// with 17 or more clues can still have several solutions.
+    pub fn below_minimum_clues(&self) -> bool {
+        self.given_count() < 17
+    }
 
-        board.try_place((0, 0), 0); // clear placed number from previous test.
+    /// Returns the empty cell with the most (or, if `most` is false, the fewest) candidates.
+    /// Ties are broken in row-major order. Returns None if the board has no empty cells.
+    pub fn cell_by_candidate_count(&self, most: bool) -> Option<(u8, u8)> {
+        let mut best: Option<((u8, u8), u8)> = None;
+        for r in 0..9 {
+            for c in 0..9 {
+                if self.board[r as usize][c as usize] != 0 {
+                    continue;
+                }
+                let count = self.candidate_count((r, c)).unwrap();
+                let is_better = match best {
+                    None => true,
+                    Some((_, best_count)) if most => count > best_count,
+                    Some((_, best_count)) => count < best_count,
+                };
+                if is_better {
+                    best = Some(((r, c), count));
+                }
+            }
+        }
+        best.map(|(cell, _)| cell)
+    }
 
-        // Trying to place on an initial number.
-        assert!(board.try_place((0, 2), 5).is_err());
+    /// Returns how many values could legally be placed in a cell, without allocating.
+    /// A filled cell has no candidates; returns None if the cell is out of bounds.
+    pub fn candidate_count(&self, cell: (u8, u8)) -> Option<u8> {
+        self.get(cell)?;
+        Some(self.candidates_mask(cell).count_ones() as u8)
+    }
 
-        // Trying to place out of bounds.
-        assert!(board.try_place((9, 9), 5).is_err());
+    /// Returns every value 1-9 that could legally be placed in a cell, in ascending order.
+    /// Empty if the cell is filled or out of bounds.
+    pub fn candidates(&self, cell: (u8, u8)) -> Vec<u8> {
+        let mask = self.candidates_mask(cell);
+        (1..=9).filter(|&num| mask & (1 << num) != 0).collect()
+    }
 
-        // Trying to place an invalid number.
-        assert!(board.try_place((0, 0), 10).is_err());
+    /// Bitmask form of `candidates`: bit `n` is set if `n` could legally be placed in the
+    /// cell. Zero if the cell is filled or out of bounds.
+    pub fn candidates_mask(&self, cell: (u8, u8)) -> u16 {
+        match (self.get(cell), self.peer_value_mask(cell)) {
+            (Some(0), Some(peers)) => !peers & 0x3FE,
+            _ => 0,
+        }
+    }
 
-        // Trying to place a number that conflicts with a row.
-        assert!(board.try_place((0, 0), 9).is_err());
+    /// Returns the digits present among the 20 peers of a cell (same row, column or box,
+    /// excluding the cell itself) as a bitmask where bit `n` is set if `n` is present.
+    /// The candidates of an empty cell are therefore `!mask & 0x3FE`. Returns None if the
+    /// cell is out of bounds.
+    pub fn peer_value_mask(&self, cell: (u8, u8)) -> Option<u16> {
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        if r >= 9 || c >= 9 {
+            return None;
+        }
+
+        // An empty cell contributes nothing to its own masks.
+        if self.board[r][c] == 0 {
+            return Some(self.row_masks[r] | self.col_masks[c] | self.box_masks[Self::box_of(r, c)]);
+        }
+
+        let mut mask: u16 = 0;
+        for i in 0..9 {
+            if i != c { mask |= 1 << self.board[r][i]; }
+            if i != r { mask |= 1 << self.board[i][c]; }
+        }
+        let box_start_row = r - r % 3;
+        let box_start_col = c - c % 3;
+        for i in box_start_row..box_start_row + 3 {
+            for j in box_start_col..box_start_col + 3 {
+                if i != r || j != c { mask |= 1 << self.board[i][j]; }
+            }
+        }
+        // Empty peers set bit 0, which isn't a digit.
+        Some(mask & 0x3FE)
+    }
+
+    /// Yields all 27 units (rows, then columns, then boxes) as their kind, index 0-8,
+    /// and nine cell coordinates. Boxes are indexed in reading order.
+    pub fn units(&self) -> impl Iterator<Item = (UnitKind, u8, [(u8, u8); 9])> {
+        [UnitKind::Row, UnitKind::Column, UnitKind::Box]
+            .into_iter()
+            .flat_map(|kind| (0..9).map(move |index| (kind, index, Self::unit_cells(kind, index))))
+    }
+
+    /// Returns every filled cell whose number repeats elsewhere in its row, column or box,
+    /// in row-major order. Starting numbers and player entries are treated alike, so both
+    /// cells of a clash are reported.
+    pub fn conflicts(&self) -> Vec<(u8, u8)> {
+        let mut clashing = [[false; 9]; 9];
+        for (_, _, cells) in self.units() {
+            let value = |&(r, c): &(u8, u8)| self.board[r as usize][c as usize];
+            for cell in &cells {
+                let num = value(cell);
+                if num != 0 && cells.iter().filter(|other| value(other) == num).count() > 1 {
+                    clashing[cell.0 as usize][cell.1 as usize] = true;
+                }
+            }
+        }
+        self.iter_cells()
+            .map(|(cell, _)| cell)
+            .filter(|&(r, c)| clashing[r as usize][c as usize])
+            .collect()
+    }
+
+    /// Returns the box (0-8, in reading order) that holds `cell`, or None if it is off the board.
+    pub fn box_index(cell: (u8, u8)) -> Option<u8> {
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        (r < 9 && c < 9).then(|| Self::box_of(r, c) as u8)
+    }
+
+    /// Returns the top-left cell of a box (0-8, in reading order), or None for any other index.
+    pub fn box_origin(box_index: u8) -> Option<(u8, u8)> {
+        (box_index < 9).then(|| ((box_index / 3) * 3, (box_index % 3) * 3))
+    }
+
+    // The box index of (r, c), which must be on the board.
+    fn box_of(r: usize, c: usize) -> usize {
+        (r / 3) * 3 + c / 3
+    }
+
+    /// Returns the nine values of row `r`, or None if `r` is not 0-8.
+    pub fn row(&self, r: u8) -> Option<[u8; 9]> {
+        self.unit_values(UnitKind::Row, r)
+    }
+
+    /// Returns the nine values of column `c`, top to bottom, or None if `c` is not 0-8.
+    pub fn col(&self, c: u8) -> Option<[u8; 9]> {
+        self.unit_values(UnitKind::Column, c)
+    }
+
+    /// Returns the nine values of a box in reading order. Boxes are numbered 0-8 in reading
+    /// order too; returns None for any other index.
+    pub fn box_cells(&self, box_index: u8) -> Option<[u8; 9]> {
+        self.unit_values(UnitKind::Box, box_index)
+    }
+
+    // The values of a unit in reading order, or None if index is not 0-8.
+    fn unit_values(&self, kind: UnitKind, index: u8) -> Option<[u8; 9]> {
+        if index >= 9 {
+            return None;
+        }
+        Some(Self::unit_cells(kind, index).map(|(r, c)| self.board[r as usize][c as usize]))
+    }
+
+    /// Looks for a hidden single in one unit: a digit that can only go in one empty cell
+    /// of that unit. Digits are checked in ascending order and the first hit is returned.
+    /// Returns None if there is none or `index` is not 0-8.
+    pub fn hidden_single_in_unit(&self, kind: UnitKind, index: u8) -> Option<Placement> {
+        if index >= 9 {
+            return None;
+        }
+        let cells = Self::unit_cells(kind, index);
+        for num in 1..=9 {
+            let mut spots = cells.iter().filter(|&&cell| {
+                self.get(cell) == Some(0) && self.is_placement_valid(cell, num)
+            });
+            if let (Some(&cell), None) = (spots.next(), spots.next()) {
+                return Some((cell, num));
+            }
+        }
+        None
+    }
+
+    // Coordinates of the cells in a unit, in reading order. Assumes index is 0-8.
+    fn unit_cells(kind: UnitKind, index: u8) -> [(u8, u8); 9] {
+        core::array::from_fn(|i| {
+            let i = i as u8;
+            match kind {
+                UnitKind::Row => (index, i),
+                UnitKind::Column => (i, index),
+                UnitKind::Box => ((index / 3) * 3 + i / 3, (index % 3) * 3 + i % 3),
+            }
+        })
+    }
+
+    /// Whether `num` appears nowhere among the cell's 20 peers, in O(1). Only the Sudoku
+    /// rule is checked: unlike `try_place`, a filled cell or a starting number is no obstacle,
+    /// so a solver can ask about any cell. False for a cell off the board or a `num` outside
+    /// 1-9.
+    pub fn is_placement_valid(&self, cell: (u8, u8), num: u8) -> bool {
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        if r >= 9 || c >= 9 || !Self::is_valid_symbol(num) {
+            return false;
+        }
+
+        // The cell's own value is in its masks, so fall back to checking its peers.
+        if self.board[r][c] == num {
+            return self.peer_value_mask(cell).unwrap() & (1 << num) == 0;
+        }
+
+        let used = self.row_masks[r] | self.col_masks[c] | self.box_masks[Self::box_of(r, c)];
+        used & (1 << num) == 0
+    }
+
+    // Crate level helper -- a copy of the board with every player entry removed.
+    pub(crate) fn givens_only(&self) -> SudokuBoard {
+        let mut board = *self;
+        for r in 0..9 {
+            for c in 0..9 {
+                if !self.initial_mask[r][c] {
+                    board.board[r][c] = 0;
+                }
+            }
+        }
+        board.recompute_masks();
+        board
+    }
+
+    /// Writes `num` to a cell, or clears it with 0, with no checks at all: starting numbers
+    /// can be overwritten and conflicts are allowed. Meant for solvers that need to undo
+    /// their own moves; prefer `try_place` or `place_fast` wherever they fit. `num` must be
+    /// 0-9.
+    ///
+    /// Panics if `cell` is off the board.
+    pub fn internal_place(&mut self, cell: (u8, u8), num: u8) {
+        debug_assert!(num <= 9, "internal_place: {} is not between 0 and 9", num);
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        let old = self.board[r][c];
+        self.write(r, c, num);
+        if old != 0 && old != num {
+            // The number may have been repeated, so a unit keeps its bit while a copy is left.
+            let bit = 1u16 << old;
+            let b = Self::box_of(r, c);
+            if self.board[r].contains(&old) {
+                self.row_masks[r] |= bit;
+            }
+            if self.board.iter().any(|row| row[c] == old) {
+                self.col_masks[c] |= bit;
+            }
+            if Self::unit_cells(UnitKind::Box, b as u8).iter().any(|&(br, bc)| self.board[br as usize][bc as usize] == old) {
+                self.box_masks[b] |= bit;
+            }
+        }
+    }
+
+    // Writes a cell and updates the masks in O(1). Only right when the old number is not
+    // repeated in any of the cell's units, which place_fast and unplace_fast guarantee.
+    fn write(&mut self, r: usize, c: usize, num: u8) {
+        let b = Self::box_of(r, c);
+        let old = self.board[r][c];
+        if old != 0 {
+            let clear = !(1u16 << old);
+            self.row_masks[r] &= clear;
+            self.col_masks[c] &= clear;
+            self.box_masks[b] &= clear;
+        }
+        if num != 0 {
+            let bit = 1u16 << num;
+            self.row_masks[r] |= bit;
+            self.col_masks[c] |= bit;
+            self.box_masks[b] |= bit;
+        }
+        self.board[r][c] = num;
+    }
+
+    // Rebuilds the row, column and box masks from the grid, after bulk changes.
+    fn recompute_masks(&mut self) {
+        self.row_masks = [0; 9];
+        self.col_masks = [0; 9];
+        self.box_masks = [0; 9];
+        for r in 0..9 {
+            for c in 0..9 {
+                let num = self.board[r][c];
+                if num != 0 {
+                    let bit = 1u16 << num;
+                    self.row_masks[r] |= bit;
+                    self.col_masks[c] |= bit;
+                    self.box_masks[Self::box_of(r, c)] |= bit;
+                }
+            }
+        }
+    }
+
+    /// Places `num` in an empty cell in O(1), skipping the checks done by `try_place`.
+    /// Intended for custom solvers that already track which moves are legal.
+    ///
+    /// Preconditions (checked only in debug builds): `cell` is on the board, is not a
+    /// starting number and is empty, `num` is 1-9, and `num` does not conflict with any peer.
+    pub fn place_fast(&mut self, cell: (u8, u8), num: u8) {
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        debug_assert!(r < 9 && c < 9, "place_fast: cell {:?} is out of bounds", cell);
+        debug_assert!(!self.initial_mask[r][c], "place_fast: cell {:?} is a starting number", cell);
+        debug_assert!(self.board[r][c] == 0, "place_fast: cell {:?} is not empty", cell);
+        debug_assert!(Self::is_valid_symbol(num), "place_fast: {} is not between 1 and 9", num);
+        debug_assert!(self.is_placement_valid(cell, num), "place_fast: {} conflicts at {:?}", num, cell);
+        self.write(r, c, num);
+    }
+
+    /// Undoes a `place_fast`, emptying the cell in O(1).
+    ///
+    /// Preconditions (checked only in debug builds): `cell` is on the board, is not a
+    /// starting number, and currently holds `num`.
+    pub fn unplace_fast(&mut self, cell: (u8, u8), num: u8) {
+        let (r, c) = (cell.0 as usize, cell.1 as usize);
+        debug_assert!(r < 9 && c < 9, "unplace_fast: cell {:?} is out of bounds", cell);
+        debug_assert!(!self.initial_mask[r][c], "unplace_fast: cell {:?} is a starting number", cell);
+        debug_assert!(self.board[r][c] == num, "unplace_fast: cell {:?} does not hold {}", cell, num);
+        self.write(r, c, 0);
+    }
+
+    /// Whether `num` is a digit a cell can hold, 1 to 9. 0 marks an empty cell, so it is not
+    /// a symbol, even though `try_place` accepts it to clear a cell.
+    pub fn is_valid_symbol(num: u8) -> bool {
+        (1..=9).contains(&num)
+    }
+    
+    /// Checks a grid for a number repeated within any row, column or box, returning false at
+    /// the first repeat. Empty cells are ignored, and a value above 9 makes the grid invalid.
+    /// Runs in a single pass with no allocation.
+    pub fn is_valid_config(config: &[[u8; 9]; 9]) -> bool {
+        // Bit n of rows[r] is set once n has appeared in row r; likewise for columns and boxes.
+        let mut rows = [0u16; 9];
+        let mut cols = [0u16; 9];
+        let mut boxes = [0u16; 9];
+        for (r, row) in config.iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                if value == 0 {
+                    continue;
+                }
+                if !Self::is_valid_symbol(value) {
+                    return false;
+                }
+                let bit = 1u16 << value;
+                let b = Self::box_of(r, c);
+                if (rows[r] | cols[c] | boxes[b]) & bit != 0 {
+                    return false;
+                }
+                rows[r] |= bit;
+                cols[c] |= bit;
+                boxes[b] |= bit;
+            }
+        }
+        true
+    }
+}
+
+// Text rendering, left out of minimal builds.
+#[cfg(feature = "render")]
+impl SudokuBoard {
+    #[cfg(feature = "std")]
+    pub fn print(&self) {
+        // Print the Sudoku Board in a human readable
+        println!("{}", self);
+    }
+
+    // The bordered grid, rendering cells for which `marked(row, col)` is true as `[n]`.
+    // Separators are sized from the rendered rows so the borders always line up.
+    fn grid_lines(&self, marked: impl Fn(usize, usize) -> bool) -> Vec<String> {
+        let rows: Vec<String> = self.board.iter().enumerate().map(|(row_index, row)| {
+            let mut line = String::from("|");
+            for (col_index, &element) in row.iter().enumerate() {
+                if marked(row_index, col_index) {
+                    line.push_str(&format!("[{}]", element));
+                } else {
+                    line.push_str(&format!(" {} ", element));
+                }
+                if (col_index + 1) % 3 == 0 {
+                    line.push('|');
+                }
+            }
+            line
+        }).collect();
+
+        let separator = "-".repeat(rows[0].len());
+        let mut lines = vec![separator.clone()];
+        for (row_index, row) in rows.into_iter().enumerate() {
+            lines.push(row);
+            if (row_index + 1) % 3 == 0 {
+                lines.push(separator.clone());
+            }
+        }
+        lines
+    }
+
+    /// Renders the board with a border around every cell, drawn heavier around the 3x3
+    /// boxes. With `unicode` the borders use box-drawing characters and empty cells show as
+    /// `·`; otherwise plain ASCII is used (`=` and `|` for boxes, `-` and `:` for cells,
+    /// `.` for empty cells). Lines end with `\n`.
+    pub fn to_pretty_string(&self, unicode: bool) -> String {
+        // [left, fill, cell junction, box junction, right] for each kind of horizontal line.
+        // Vertical bars are [cell, box].
+        let (top, thin, thick, bottom, bars, empty) = if unicode {
+            (
+                ['╔', '═', '╤', '╦', '╗'],
+                ['╟', '─', '┼', '╫', '╢'],
+                ['╠', '═', '╪', '╬', '╣'],
+                ['╚', '═', '╧', '╩', '╝'],
+                ['│', '║'],
+                '·',
+            )
+        } else {
+            let thick = ['+', '=', '+', '+', '+'];
+            (thick, ['+', '-', '+', '+', '+'], thick, thick, [':', '|'], '.')
+        };
+        let border = |[left, fill, cell, block, right]: [char; 5]| {
+            let mut line = String::from(left);
+            for c in 0..9 {
+                line.extend([fill; 3]);
+                line.push(if c == 8 { right } else if c % 3 == 2 { block } else { cell });
+            }
+            line.push('\n');
+            line
+        };
+
+        let mut out = border(top);
+        for (r, row) in self.board.iter().enumerate() {
+            out.push(bars[1]);
+            for (c, &value) in row.iter().enumerate() {
+                let shown = if value == 0 { empty } else { (b'0' + value) as char };
+                out.extend([' ', shown, ' ', bars[usize::from(c % 3 == 2)]]);
+            }
+            out.push('\n');
+            if r < 8 {
+                out.push_str(&border(if r % 3 == 2 { thick } else { thin }));
+            }
+        }
+        out.push_str(&border(bottom));
+        out
+    }
+
+    /// Renders the grid as `print()` does, but when `mark_givens` is true the starting
+    /// numbers show as `[n]` so they stand out from the player's entries. With `mark_givens`
+    /// false the grid is unmarked. Lines end with `\n`.
+    pub fn to_annotated_string(&self, mark_givens: bool) -> String {
+        self.grid_lines(|r, c| mark_givens && self.initial_mask[r][c])
+            .iter()
+            .map(|line| format!("{}\n", line))
+            .collect()
+    }
+
+    /// Renders this board and `other` side by side, marking each cell whose value differs
+    /// between them as `[n]` instead of ` n `.
+    pub fn diff_string(&self, other: &SudokuBoard) -> String {
+        let left = self.diff_lines(other);
+        let right = other.diff_lines(self);
+        left.iter()
+            .zip(&right)
+            .map(|(l, r)| format!("{}   {}\n", l, r))
+            .collect()
+    }
+
+    /// Prints this board and `other` side by side with differing cells marked.
+    #[cfg(feature = "std")]
+    pub fn print_diff(&self, other: &SudokuBoard) {
+        print!("{}", self.diff_string(other));
+    }
+
+    // The grid lines of print(), marking cells that differ from `other`.
+    fn diff_lines(&self, other: &SudokuBoard) -> Vec<String> {
+        self.grid_lines(|r, c| self.board[r][c] != other.board[r][c])
+    }
+}
+
+/// Formats the board as `print()` shows it: a titled banner followed by the bordered grid.
+/// The output has no trailing newline, so `println!("{}", board)` prints it exactly once.
+#[cfg(feature = "render")]
+impl fmt::Display for SudokuBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let grid = self.grid_lines(|_, _| false);
+        let width = grid[0].len();
+        writeln!(f, "{}", grid[0])?;
+        writeln!(f, "|{:^1$}|", "CURRENT BOARD STATE", width - 2)?;
+        write!(f, "{}", grid.join("\n"))
+    }
+}
+
+/// Parses an 81-cell row-major puzzle string such as `"53..7....6..195..."`. Digits `1-9`
+/// are givens, `0` or `.` are empty cells, and whitespace (including newlines) is ignored,
+/// as are the `|`, `+` and `-` of box borders. A flat line, nine lines of nine, and a
+/// bordered grid all parse the same. Errors are `SudokuError`s, so `line.parse()?` works
+/// in functions returning `Box<dyn Error>`.
+impl FromStr for SudokuBoard {
+    type Err = SudokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let options = ParseOptions {
+            skip_whitespace: true,
+            skip_separators: true,
+            allow_dot: true,
+            strict_length: true,
+        };
+        Self::from_str_opts(s, &options)
+    }
+}
+
+// Wire format for serde: the grid and which cells are givens. The unit masks are rebuilt on
+// the way in rather than trusted from the input.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardRepr {
+    board: [[u8; 9]; 9],
+    initial_mask: [[bool; 9]; 9],
+}
+
+#[cfg(feature = "serde")]
+impl From<SudokuBoard> for BoardRepr {
+    fn from(board: SudokuBoard) -> Self {
+        BoardRepr {
+            board: board.board,
+            initial_mask: board.initial_mask,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl BoardRepr {
+    // Validates the grid like `from` does, and rejects givens on empty cells.
+    fn into_board(self) -> Result<SudokuBoard, SudokuError> {
+        SudokuBoard::from_state(self.board, self.initial_mask)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SudokuBoard {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BoardRepr::from(*self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SudokuBoard {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = BoardRepr::deserialize(deserializer)?;
+        repr.into_board().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A board with an undo/redo history, for interactive play. Every `set` that changes a
+/// cell is recorded; starting numbers can't be set, so they are never touched.
+#[derive(Debug, Clone)]
+pub struct UndoableBoard {
+    board: SudokuBoard,
+    undo_stack: Vec<Move>,
+    redo_stack: Vec<Move>,
+}
+
+// One recorded change: the cell, its value before, and its value after.
+#[derive(Debug, Clone, Copy)]
+struct Move {
+    cell: (u8, u8),
+    before: u8,
+    after: u8,
+}
+
+impl UndoableBoard {
+    /// Starts an empty history on `board`.
+    pub fn new(board: SudokuBoard) -> Self {
+        UndoableBoard {
+            board,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    /// Borrows the current board.
+    pub fn board(&self) -> &SudokuBoard {
+        &self.board
+    }
+
+    /// Places `num` (or clears with 0) exactly like `SudokuBoard::try_place`. A change is
+    /// recorded and clears anything that could have been redone.
+    pub fn set(&mut self, cell: (u8, u8), num: u8) -> Result<(), SudokuError> {
+        let before = self.board.get(cell).ok_or(SudokuError::OutOfBounds { row: cell.0, col: cell.1 })?;
+        self.board.try_place(cell, num)?;
+        if before != num {
+            self.undo_stack.push(Move { cell, before, after: num });
+            self.redo_stack.clear();
+        }
+        Ok(())
+    }
+
+    /// Takes back the latest change. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(last) = self.undo_stack.pop() else {
+            return false;
+        };
+        // The earlier value was valid when it was replaced, so it is valid to restore.
+        self.board.internal_place(last.cell, last.before);
+        self.redo_stack.push(last);
+        true
+    }
+
+    /// Reapplies the latest undone change. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.board.internal_place(next.cell, next.after);
+        self.undo_stack.push(next);
+        true
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    // A valid Sudoku configuration for use in tests.
+    fn valid_config() -> [[u8; 9]; 9] {
+        [
+            [0, 0, 6, 0, 4, 0, 0, 9, 7],
+            [0, 4, 0, 7, 3, 0, 0, 1, 0],
+            [0, 1, 7, 0, 9, 2, 0, 3, 0],
+            [6, 0, 0, 0, 7, 0, 0, 8, 0],
+            [1, 0, 5, 0, 6, 0, 9, 0, 3],
+            [0, 2, 0, 0, 1, 0, 0, 0, 6],
+            [0, 5, 0, 9, 8, 0, 1, 6, 0],
+            [0, 9, 0, 0, 5, 6, 0, 7, 0],
+            [8, 6, 0, 0, 2, 0, 3, 0, 0],
+        ]
+    }
+
+    // An invalid configuration with a duplicate number in the second row.
+    fn invalid_row_config() -> [[u8; 9]; 9] {
+        [
+            [0, 0, 0, 2, 6, 0, 7, 0, 1],
+            [6, 8, 0, 0, 7, 0, 0, 8, 0], // duplicate 8
+            [1, 9, 0, 0, 0, 4, 5, 0, 0],
+            [8, 2, 0, 1, 0, 0, 0, 4, 0],
+            [0, 0, 4, 6, 0, 2, 9, 0, 0],
+            [0, 5, 0, 0, 0, 3, 0, 2, 8],
+            [0, 0, 9, 3, 0, 0, 0, 7, 4],
+            [0, 4, 0, 0, 5, 0, 0, 3, 6],
+            [7, 0, 3, 0, 1, 8, 0, 0, 0]
+        ]
+    }
+
+    // An invalid configuration with a duplicate number in the second column.
+    fn invalid_col_config() -> [[u8; 9]; 9] {
+        [
+            [0, 9, 0, 2, 6, 0, 7, 0, 1],
+            [6, 0, 0, 0, 7, 0, 0, 8, 0],
+            [1, 9, 0, 0, 0, 4, 5, 0, 0], // duplicate 9
+            [8, 2, 0, 1, 0, 0, 0, 4, 0],
+            [0, 0, 4, 6, 0, 2, 9, 0, 0],
+            [0, 5, 0, 0, 0, 3, 0, 2, 8],
+            [0, 0, 9, 3, 0, 0, 0, 7, 4],
+            [0, 4, 0, 0, 5, 0, 0, 3, 6],
+            [7, 0, 3, 0, 1, 8, 0, 0, 0]
+        ]
+    }
+
+    // An invalid configuration with a duplicate number in the top-right(1, 3) box.
+    fn invalid_box_config() -> [[u8; 9]; 9] {
+        [
+            [0, 0, 0, 2, 6, 0, 7, 0, 1],
+            [6, 0, 0, 0, 7, 0, 0, 8, 0],
+            [1, 9, 0, 0, 0, 4, 5, 0, 7], // duplicate 7
+            [8, 2, 0, 1, 0, 0, 0, 4, 0],
+            [0, 0, 4, 6, 0, 2, 9, 0, 0],
+            [0, 5, 0, 0, 0, 3, 0, 2, 8],
+            [0, 0, 9, 3, 0, 0, 0, 7, 4],
+            [0, 4, 0, 0, 5, 0, 0, 3, 6],
+            [7, 0, 3, 0, 1, 8, 0, 0, 0]
+        ]
+    }
+
+    #[test]
+    fn test_init_from_valid_config() {
+        // The from() function should succeed with a valid configuration.
+        assert!(SudokuBoard::from(valid_config()).is_ok());
+    }
+
+    #[test]
+    fn test_init_from_invalid_configs() {
+        // The from() function should return an error for an invalid configuration.
+        assert!(SudokuBoard::from(invalid_row_config()).is_err());
+        assert!(SudokuBoard::from(invalid_col_config()).is_err());
+        assert!(SudokuBoard::from(invalid_box_config()).is_err());
+    }
+
+    // The valid configuration as a flat string.
+    fn valid_line() -> String {
+        valid_config().iter().flatten().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_str_opts_default_is_strict() {
+        let options = ParseOptions::default();
+        let board = SudokuBoard::from_str_opts(&valid_line(), &options).unwrap();
+        assert_eq!(board.get_board(), &valid_config());
+
+        // Whitespace, dots, and extra or missing cells are all rejected.
+        assert!(SudokuBoard::from_str_opts(&format!("{} ", valid_line()), &options).is_err());
+        assert!(SudokuBoard::from_str_opts(&valid_line().replace('0', "."), &options).is_err());
+        assert!(SudokuBoard::from_str_opts(&format!("{}0", valid_line()), &options).is_err());
+        assert!(SudokuBoard::from_str_opts(&valid_line()[..80], &options).is_err());
+    }
+
+    #[test]
+    fn test_from_str_opts_lenient() {
+        let options =
+            ParseOptions { skip_whitespace: true, skip_separators: false, allow_dot: true, strict_length: false };
+        let line = valid_line().replace('0', ".");
+        let messy = format!("{}\n{}  \n", &line[..40], &line[40..]);
+        let board = SudokuBoard::from_str_opts(&messy, &options).unwrap();
+        assert_eq!(board.get_board(), &valid_config());
+
+        // Trailing data past the 81st cell is ignored, but short input still fails.
+        assert!(SudokuBoard::from_str_opts(&format!("{}#1", line), &options).is_ok());
+        assert!(SudokuBoard::from_str_opts(&line[..80], &options).is_err());
+
+        // Leniency never extends to invalid characters or invalid grids.
+        assert!(SudokuBoard::from_str_opts(&line.replacen('.', "x", 1), &options).is_err());
+        assert!(SudokuBoard::from_str_opts(&line.replacen('.', "6", 1), &options).is_err());
+    }
+
+    #[test]
+    fn test_parse_reports_sudoku_errors() {
+        let board: SudokuBoard = valid_line().parse().unwrap();
+        assert_eq!(board.get_board(), &valid_config());
+
+        assert_eq!(valid_line()[..80].parse::<SudokuBoard>().err(), Some(SudokuError::InvalidLength));
+        let bad_char = valid_line().replacen('0', "x", 1).parse::<SudokuBoard>();
+        assert_eq!(bad_char.err(), Some(SudokuError::InvalidCharacter('x')));
+        // A second 6 in the first row.
+        let repeat = valid_line().replacen('0', "6", 1).parse::<SudokuBoard>();
+        assert_eq!(repeat.err(), Some(SudokuError::InvalidConfig));
+    }
+
+    #[test]
+    fn test_parse_puzzle_string() {
+        let mut board: SudokuBoard = valid_line().replace('0', ".").parse().unwrap();
+        assert_eq!(board.get_board(), &valid_config());
+        assert!(board.try_place((0, 2), 5).is_err()); // parsed digits are givens
+
+        // Zeros, dots, and whitespace between rows can be mixed freely.
+        let rows: Vec<String> = valid_config().iter()
+            .map(|row| row.iter().map(|v| format!("{} ", v)).collect())
+            .collect();
+        let grid = rows.join("\n");
+        let board: SudokuBoard = grid.replacen('0', ".", 3).parse().unwrap();
+        assert_eq!(board.get_board(), &valid_config());
+    }
+
+    #[test]
+    fn test_parse_puzzle_string_errors() {
+        assert_eq!(valid_line()[..80].parse::<SudokuBoard>().err(), Some(SudokuError::InvalidLength));
+        assert_eq!(format!("{}0", valid_line()).parse::<SudokuBoard>().err(), Some(SudokuError::InvalidLength));
+        assert_eq!(
+            valid_line().replacen('0', "x", 1).parse::<SudokuBoard>().err(),
+            Some(SudokuError::InvalidCharacter('x'))
+        );
+
+        // An invalid grid reports the same error as from().
+        let invalid: String = invalid_row_config().iter().flatten().map(|v| v.to_string()).collect();
+        assert_eq!(invalid.parse::<SudokuBoard>().err(), Some(SudokuError::InvalidConfig));
+    }
+
+    #[test]
+    fn test_parse_flat_lined_and_bordered_alike() {
+        let flat = valid_line();
+        let lined = valid_config()
+            .iter()
+            .map(|row| row.iter().map(|v| v.to_string()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let bordered = valid_config()
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                let cells: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+                let boxes: Vec<String> = cells.chunks(3).map(|chunk| chunk.join(" ")).collect();
+                let line = format!("| {} |", boxes.join(" | "));
+                if r % 3 == 0 { format!("+-------+-------+-------+\n{}", line) } else { line }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n+-------+-------+-------+\n";
+
+        for input in [&flat, &lined, &bordered, &bordered.replace('0', ".")] {
+            let board: SudokuBoard = input.parse().unwrap();
+            assert_eq!(board.get_board(), &valid_config(), "{}", input);
+        }
+
+        // Separators are skipped, not counted as cells, so a short grid is still short.
+        let short = bordered.replacen('3', "-", 1).parse::<SudokuBoard>();
+        assert_eq!(short.err(), Some(SudokuError::InvalidLength));
+        let bad = flat.replacen('0', "#", 1).parse::<SudokuBoard>();
+        assert_eq!(bad.err(), Some(SudokuError::InvalidCharacter('#')));
+    }
+
+    #[test]
+    fn test_board_equality() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board, SudokuBoard::from(valid_config()).unwrap());
+        assert_eq!(board, board.to_line().parse().unwrap());
+
+        let mut placed = board;
+        placed.try_place((0, 0), 3).unwrap();
+        assert_ne!(placed, board);
+
+        // Same numbers, but (0,0) is a given in one and a player entry in the other.
+        let mut given = board;
+        given.set_given((0, 0), 3).unwrap();
+        assert_eq!(given.get_board(), placed.get_board());
+        assert_ne!(given, placed);
+    }
+
+    #[test]
+    fn test_board_hash() {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+        let hash = |board: &SudokuBoard| {
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        let copy: SudokuBoard = board.to_line().parse().unwrap();
+        assert_eq!(hash(&board), hash(&copy));
+
+        let mut placed = board;
+        placed.try_place((0, 0), 3).unwrap();
+        let mut given = board;
+        given.set_given((0, 0), 3).unwrap();
+
+        let mut set = HashSet::new();
+        assert!(set.insert(board));
+        assert!(!set.insert(copy));
+        assert!(set.insert(placed));
+        assert!(set.insert(given));
+        assert!(!set.insert(placed));
+        assert_eq!(set.len(), 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.try_place((0, 0), 3).unwrap();
+        board.set_given((0, 1), 8).unwrap();
+
+        let json = serde_json::to_string(&board).unwrap();
+        let restored: SudokuBoard = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, board);
+        let mut restored = restored;
+        assert_eq!(restored.try_place((0, 1), 0), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(restored.try_place((0, 0), 0), Ok(()));
+
+        // A duplicate in row 0 is rejected rather than producing a corrupt board.
+        let corrupt = json.replacen("[3,8,6", "[6,8,6", 1);
+        assert!(serde_json::from_str::<SudokuBoard>(&corrupt).is_err());
+
+        // So is a given on an empty cell.
+        let mut repr: serde_json::Value = serde_json::from_str(&json).unwrap();
+        repr["initial_mask"][0][2] = serde_json::Value::Bool(false);
+        repr["initial_mask"][0][3] = serde_json::Value::Bool(true);
+        assert!(serde_json::from_value::<SudokuBoard>(repr.clone()).is_err());
+        repr["initial_mask"][0][3] = serde_json::Value::Bool(false);
+        assert!(serde_json::from_value::<SudokuBoard>(repr).is_ok());
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let start = SudokuBoard::from(valid_config()).unwrap();
+        let mut game = UndoableBoard::new(start);
+        assert!(!game.undo());
+        assert!(!game.redo());
+
+        game.set((0, 0), 3).unwrap();
+        game.set((0, 0), 3).unwrap(); // No change, so nothing is recorded.
+        game.set((0, 0), 0).unwrap();
+        game.set((0, 0), 5).unwrap();
+        assert_eq!(game.set((0, 2), 1), Err(SudokuError::CannotModifyInitial));
+
+        assert!(game.undo());
+        assert_eq!(game.board().get((0, 0)), Some(0));
+        assert!(game.undo());
+        assert_eq!(game.board().get((0, 0)), Some(3));
+        assert!(game.undo());
+        assert_eq!(*game.board(), start);
+        assert!(!game.undo());
+        assert_eq!(*game.board(), start);
+
+        assert!(game.redo());
+        assert_eq!(game.board().get((0, 0)), Some(3));
+        assert!(masks_in_sync(game.board()));
+
+        // A fresh move after an undo drops the rest of the redo history.
+        game.set((0, 1), 8).unwrap();
+        assert!(!game.redo());
+        assert!(game.undo());
+        assert!(game.undo());
+        assert_eq!(*game.board(), start);
+        assert!(masks_in_sync(game.board()));
+    }
+
+    #[test]
+    fn test_from_state() {
+        // A game in progress: two givens and one player entry.
+        let mut saved = SudokuBoard::empty();
+        saved.set_given((0, 0), 5).unwrap();
+        saved.set_given((4, 4), 7).unwrap();
+        saved.try_set((0, 1), 1).unwrap();
+
+        let mut loaded = SudokuBoard::from_state(*saved.get_board(), *saved.get_givens()).unwrap();
+        assert_eq!(loaded, saved);
+        assert_eq!(loaded.try_set((0, 0), 0), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(loaded.try_set((4, 4), 0), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(loaded.try_set((0, 1), 0), Ok(()));
+        assert_eq!(loaded.try_set((0, 1), 2), Ok(()));
+
+        // A given must sit on a filled cell, and the grid must follow the rules.
+        let mut givens = *saved.get_givens();
+        givens[8][8] = true;
+        assert_eq!(SudokuBoard::from_state(*saved.get_board(), givens), Err(SudokuError::InvalidConfig));
+        let mut grid = *saved.get_board();
+        grid[0][2] = 5;
+        assert_eq!(SudokuBoard::from_state(grid, *saved.get_givens()), Err(SudokuError::InvalidConfig));
+        grid[0][2] = 10;
+        assert_eq!(SudokuBoard::from_state(grid, *saved.get_givens()), Err(SudokuError::InvalidValue(10)));
+    }
+
+    #[test]
+    fn test_empty_has_no_locked_cells() {
+        let mut board = SudokuBoard::empty();
+        assert_eq!(board, SudokuBoard::from([[0; 9]; 9]).unwrap());
+        assert_eq!(board.empty_count(), 81);
+        assert_eq!(board.given_count(), 0);
+
+        // The classic shifted-row pattern fills every cell without a conflict.
+        for r in 0..9 {
+            for c in 0..9 {
+                let num = ((r * 3 + r / 3 + c) % 9 + 1) as u8;
+                assert_eq!(board.try_set((r as u8, c as u8), num), Ok(()));
+            }
+        }
+        assert!(board.is_solved());
+        assert!(masks_in_sync(&board));
+    }
+
+    #[test]
+    fn test_from_flat() {
+        let cells: Vec<u8> = valid_config().concat();
+        let board = SudokuBoard::from_flat(&cells).unwrap();
+        assert_eq!(board, SudokuBoard::from(valid_config()).unwrap());
+        assert_eq!(SudokuBoard::from_flat(board.as_flat()), Ok(board));
+
+        assert_eq!(SudokuBoard::from_flat(&cells[..80]), Err(SudokuError::InvalidLength));
+        assert_eq!(SudokuBoard::from_flat(&[cells.as_slice(), &[0]].concat()), Err(SudokuError::InvalidLength));
+        assert_eq!(SudokuBoard::from_flat(&[]), Err(SudokuError::InvalidLength));
+        assert_eq!(SudokuBoard::from_flat(&invalid_box_config().concat()), Err(SudokuError::InvalidConfig));
+    }
+
+    #[test]
+    fn test_from_grid_str() {
+        let grid = "\
+            . . 6 | . 4 . | . 9 7
+            _ 4 0 | 7 3 . | . 1 .
+            . 1 7 | . 9 2 | . 3 .
+            ------+-------+------
+            6 . . | . 7 . | . 8 .
+            1 . 5 | . 6 . | 9 . 3
+            . 2 . | . 1 . | . . 6
+
+            ------+-------+------
+            .5.   | 98.   | 16.
+            .9.   | .56   | .7.
+            86.   | .2.   | 3..
+        ";
+        let board = SudokuBoard::from_grid_str(grid).unwrap();
+        assert_eq!(board, SudokuBoard::from(valid_config()).unwrap());
+
+        let line = |n: usize| grid.lines().nth(n - 1).unwrap();
+        let bad = grid.replacen("_ 4 0", "_ 4 x", 1);
+        let column = line(2).find('0').unwrap() + 1;
+        assert_eq!(SudokuBoard::from_grid_str(&bad), Err(SudokuError::InvalidGrid { line: 2, column }));
+
+        // A short row points just past its end, an extra cell points at itself.
+        let short = grid.replacen("| 9 . 3", "| 9 .", 1);
+        let column = line(6).len() - 1;
+        assert_eq!(SudokuBoard::from_grid_str(&short), Err(SudokuError::InvalidGrid { line: 6, column }));
+        let long = grid.replacen("| 9 . 3", "| 9 . 3 4", 1);
+        let column = line(6).len() + 2;
+        assert_eq!(SudokuBoard::from_grid_str(&long), Err(SudokuError::InvalidGrid { line: 6, column }));
+
+        let extra_row = format!("{}\n.........", grid);
+        let last = extra_row.lines().count();
+        assert_eq!(SudokuBoard::from_grid_str(&extra_row), Err(SudokuError::InvalidGrid { line: last, column: 1 }));
+        let missing_row = grid.replacen("86.   | .2.   | 3..", "", 1);
+        assert_eq!(SudokuBoard::from_grid_str(&missing_row), Err(SudokuError::InvalidLength));
+        let conflict = grid.replacen(". . 6", "6 . 6", 1);
+        assert_eq!(SudokuBoard::from_grid_str(&conflict), Err(SudokuError::InvalidConfig));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_to_pretty_string() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+
+        let pretty = board.to_pretty_string(true);
+        let lines: Vec<&str> = pretty.lines().collect();
+        assert_eq!(lines.len(), 19);
+        assert!(lines.iter().all(|line| line.chars().count() == 37));
+        assert_eq!(lines[0].chars().next(), Some('╔'));
+        assert_eq!(lines[0].chars().last(), Some('╗'));
+        assert_eq!(lines[18].chars().next(), Some('╚'));
+        assert_eq!(lines[18].chars().last(), Some('╝'));
+        assert_eq!(lines[1], "║ · │ · │ 6 ║ · │ 4 │ · ║ · │ 9 │ 7 ║");
+        assert_eq!(lines[2], "╟───┼───┼───╫───┼───┼───╫───┼───┼───╢");
+        assert_eq!(lines[6], "╠═══╪═══╪═══╬═══╪═══╪═══╬═══╪═══╪═══╣");
+
+        let ascii = board.to_pretty_string(false);
+        let lines: Vec<&str> = ascii.lines().collect();
+        assert_eq!(lines.len(), 19);
+        assert!(ascii.is_ascii());
+        assert_eq!(lines[0], "+===+===+===+===+===+===+===+===+===+");
+        assert_eq!(lines[1], "| . : . : 6 | . : 4 : . | . : 9 : 7 |");
+        assert_eq!(lines[2], "+---+---+---+---+---+---+---+---+---+");
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_to_annotated_string() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.try_set((0, 0), 3).unwrap();
+
+        let marked = board.to_annotated_string(true);
+        let row = marked.lines().nth(1).unwrap();
+        assert!(row.starts_with("| 3  0 [6]| 0 [4] 0 |"));
+        assert_eq!(marked.matches('[').count(), 37);
+
+        // Unmarked, it is exactly the grid part of the Display output.
+        let plain = board.to_annotated_string(false);
+        assert!(!plain.contains('['));
+        assert!(board.to_string().ends_with(plain.trim_end()));
+    }
+
+    #[test]
+    fn test_to_line_round_trip() {
+        let puzzle = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(puzzle.to_line(), valid_line());
+        assert_eq!(puzzle.to_line_with('.'), valid_line().replace('0', "."));
+
+        let parsed: SudokuBoard = puzzle.to_line().parse().unwrap();
+        assert_eq!(parsed.get_board(), puzzle.get_board());
+        let parsed: SudokuBoard = puzzle.to_line_with('.').parse().unwrap();
+        assert_eq!(parsed.get_board(), puzzle.get_board());
+
+        let solved = crate::sudoku_solver::BacktrackingSolver::run(&puzzle).unwrap();
+        assert!(!solved.to_line().contains('0'));
+        let parsed: SudokuBoard = solved.to_line().parse().unwrap();
+        assert_eq!(parsed.get_board(), solved.get_board());
+        assert_ne!(parsed, solved);
+    }
+
+    #[test]
+    fn test_get_value() {
+        // Test that get() retrieves the correct value from the board.
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.get((0, 2)), Some(6)); // Should be 6
+        assert_eq!(board.get((0, 0)), Some(0)); // Should be 0 
+        assert_eq!(board.get((9, 9)), None);   // Out of bounds
+    }
+
+    #[test]
+    fn test_try_place_error_variants() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.try_place((9, 3), 5), Err(SudokuError::OutOfBounds { row: 9, col: 3 }));
+        assert_eq!(board.try_place((0, 2), 5), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(board.try_place((0, 0), 10), Err(SudokuError::InvalidValue(10)));
+        assert_eq!(board.try_place((0, 0), 9), Err(SudokuError::Conflict));
+        board.try_place((0, 0), 3).unwrap();
+        assert_eq!(board.try_place((0, 0), 2), Err(SudokuError::CellFilled));
+        assert_eq!(SudokuBoard::from(invalid_box_config()).err(), Some(SudokuError::InvalidConfig));
+    }
+
+    // The original HashSet-based check, kept as a reference for the bitmask version.
+    fn is_valid_config_reference(config: &[[u8; 9]; 9]) -> bool {
+        let units = (0..9).flat_map(|i| {
+            [UnitKind::Row, UnitKind::Column, UnitKind::Box].map(|kind| SudokuBoard::unit_cells(kind, i))
+        });
+        for cells in units {
+            let mut seen = HashSet::new();
+            for (r, c) in cells {
+                let value = config[r as usize][c as usize];
+                if value != 0 && !seen.insert(value) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn test_is_valid_config_matches_reference() {
+        let solved = BacktrackingSolver::run(&SudokuBoard::from(valid_config()).unwrap()).unwrap();
+        let mut rng = crate::rng::Rng::new(309);
+        let (mut valid, mut invalid) = (0, 0);
+        for round in 0..2000 {
+            // Start from a solved grid, sparse random noise, or a blank one, then scribble on it.
+            let mut config = match round % 3 {
+                0 => *solved.get_board(),
+                1 => core::array::from_fn(|_| {
+                    core::array::from_fn(|_| if rng.below(4) == 0 { rng.below(10) as u8 } else { 0 })
+                }),
+                _ => [[0; 9]; 9],
+            };
+            for _ in 0..rng.below(4) {
+                config[rng.below(9)][rng.below(9)] = rng.below(10) as u8;
+            }
+
+            let expected = is_valid_config_reference(&config);
+            assert_eq!(SudokuBoard::is_valid_config(&config), expected, "{:?}", config);
+            if expected {
+                valid += 1;
+            } else {
+                invalid += 1;
+            }
+        }
+        // Both outcomes are well covered.
+        assert!(valid > 200 && invalid > 200, "{} valid, {} invalid", valid, invalid);
+
+        // Values out of range are never valid, even alone.
+        let mut config = [[0; 9]; 9];
+        config[4][4] = 10;
+        assert!(!SudokuBoard::is_valid_config(&config));
+    }
+
+    #[test]
+    fn test_is_valid_symbol_boundaries() {
+        assert!(!SudokuBoard::is_valid_symbol(0));
+        assert!(SudokuBoard::is_valid_symbol(9));
+        assert!(!SudokuBoard::is_valid_symbol(10));
+        assert!(!SudokuBoard::is_valid_symbol(255));
+
+        // Every entry point agrees: 0 clears, 9 is a real (here conflicting) digit, and
+        // anything above 9 is out of range.
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.try_set((0, 0), 0), Ok(()));
+        assert_eq!(board.try_set((0, 0), 9), Err(SudokuError::Conflict));
+        assert_eq!(board.try_set((0, 0), 10), Err(SudokuError::InvalidValue(10)));
+        assert_eq!(board.try_set((0, 0), 255), Err(SudokuError::InvalidValue(255)));
+        assert_eq!(board.set_given((0, 0), 0), Err(SudokuError::InvalidValue(0)));
+        assert_eq!(board.set_given((0, 0), 255), Err(SudokuError::InvalidValue(255)));
+
+        let mut config = valid_config();
+        config[0][0] = 10;
+        assert_eq!(SudokuBoard::from(config), Err(SudokuError::InvalidValue(10)));
+        config[0][0] = 255;
+        assert_eq!(SudokuBoard::from(config), Err(SudokuError::InvalidValue(255)));
+    }
+
+    #[test]
+    fn test_is_placement_valid_checks_peers_only() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+
+        // (0, 2) is a given 6: its own value is fine, a value from its row is not.
+        assert!(board.is_placement_valid((0, 2), 6));
+        assert!(!board.is_placement_valid((0, 2), 4));
+
+        // A filled cell is judged like an empty one, where try_place would refuse.
+        board.try_place((0, 0), 3).unwrap();
+        assert!(board.is_placement_valid((0, 0), 2));
+        assert!(!board.is_placement_valid((0, 0), 9));
+        assert_eq!(board.try_place((0, 0), 2), Err(SudokuError::CellFilled));
+    }
+
+    #[test]
+    fn test_is_placement_valid_rejects_bad_input() {
+        let board = SudokuBoard::empty();
+        assert!(!board.is_placement_valid((9, 0), 1));
+        assert!(!board.is_placement_valid((0, 9), 1));
+        for num in [0, 10, 16, 200] {
+            assert!(!board.is_placement_valid((0, 0), num));
+        }
+    }
+
+    #[test]
+    fn test_internal_place_skips_every_check() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+
+        // Givens can be cleared and rewritten, and stay givens.
+        board.internal_place((0, 2), 0);
+        assert_eq!(board.get((0, 2)), Some(0));
+        assert_eq!(board.is_given((0, 2)), Some(true));
+        assert_eq!(board.try_place((0, 2), 6), Err(SudokuError::CannotModifyInitial));
+        board.internal_place((0, 2), 6);
+        assert_eq!(board, SudokuBoard::from(valid_config()).unwrap());
+
+        // Conflicts are written as asked, with the masks still tracking the grid.
+        board.internal_place((0, 0), 6);
+        assert!(!board.is_consistent());
+        assert!(masks_in_sync(&board));
+    }
+
+    #[test]
+    fn test_clearing_a_repeat_keeps_the_other_copy_masked() {
+        let mut board = SudokuBoard::empty();
+        board.try_set((0, 0), 3).unwrap();
+        board.internal_place((0, 1), 3);
+        board.clear((0, 1)).unwrap();
+
+        // The 3 at (0, 0) still blocks the row and box.
+        assert!(masks_in_sync(&board));
+        assert_eq!(board.try_set((0, 1), 3), Err(SudokuError::Conflict));
+        assert_eq!(board.try_set((1, 1), 3), Err(SudokuError::Conflict));
+        assert_eq!(board.try_set((3, 1), 3), Ok(()));
+    }
+
+    #[test]
+    fn test_try_set_rejects_without_mutating() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.try_set((0, 0), 3).unwrap();
+        let before = board;
+
+        let rejected = [((9, 3), 5), ((0, 2), 5), ((0, 1), 10), ((0, 1), 9), ((0, 0), 2)];
+        for (cell, num) in rejected {
+            assert!(board.try_set(cell, num).is_err());
+            assert_eq!(board.as_flat(), before.as_flat());
+            assert_eq!(board, before);
+            assert!(masks_in_sync(&board));
+        }
+
+        assert_eq!(board.try_set((0, 0), 0), Ok(()));
+        assert_eq!(board.get((0, 0)), Some(0));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        let start = board;
+        board.try_set((0, 0), 3).unwrap();
+        assert_eq!(board.clear((0, 0)), Ok(()));
+        assert_eq!(board, start);
+        assert!(masks_in_sync(&board));
+
+        assert_eq!(board.clear((0, 0)), Ok(()));
+        assert_eq!(board, start);
+        assert_eq!(board.clear((0, 2)), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(board.clear((0, 9)), Err(SudokuError::OutOfBounds { row: 0, col: 9 }));
+        assert_eq!(board, start);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.try_set((0, 0), 3).unwrap();
+        board.try_set((0, 1), 8).unwrap();
+        board.try_set((2, 0), 5).unwrap();
+        board.set_given((1, 0), 9).unwrap();
+
+        board.reset();
+        let mut expected = valid_config();
+        expected[1][0] = 9;
+        assert_eq!(board, SudokuBoard::from(expected).unwrap());
+        assert!(masks_in_sync(&board));
+
+        board.reset();
+        assert_eq!(board, SudokuBoard::from(expected).unwrap());
+    }
+
+    #[test]
+    fn test_is_given_and_given_count() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.is_given((0, 2)), Some(true));
+        assert_eq!(board.is_given((0, 0)), Some(false));
+        assert_eq!(board.is_given((9, 0)), None);
+        assert_eq!(board.is_given((0, 9)), None);
+        assert_eq!(board.given_count(), 37);
+
+        // Player entries don't count as givens.
+        board.try_set((0, 0), 3).unwrap();
+        assert_eq!(board.is_given((0, 0)), Some(false));
+        assert_eq!(board.given_count(), 37);
+        board.set_given((0, 1), 8).unwrap();
+        assert_eq!(board.is_given((0, 1)), Some(true));
+        assert_eq!(board.given_count(), 38);
+    }
+
+    #[test]
+    fn test_empty_and_filled_count() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.empty_count(), 44);
+        assert_eq!(board.filled_count(), 37);
+
+        board.try_set((0, 0), 3).unwrap();
+        assert_eq!((board.empty_count(), board.filled_count()), (43, 38));
+
+        let empty = SudokuBoard::from([[0; 9]; 9]).unwrap();
+        assert_eq!((empty.empty_count(), empty.filled_count()), (81, 0));
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        assert!(board.conflicts().is_empty());
+
+        // A 6 at (0,0) clashes with the given 6 in its row and the one in its column.
+        board.internal_place((0, 0), 6);
+        assert_eq!(board.conflicts(), vec![(0, 0), (0, 2), (3, 0)]);
+
+        // A 1 at (8, 8) clashes only within its box.
+        board.internal_place((8, 8), 1);
+        assert_eq!(board.conflicts(), vec![(0, 0), (0, 2), (3, 0), (6, 6), (8, 8)]);
+
+        board.internal_place((0, 0), 0);
+        assert_eq!(board.conflicts(), vec![(6, 6), (8, 8)]);
+    }
+
+    #[test]
+    fn test_solve_in_place() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        let expected = BacktrackingSolver::run(&board).unwrap();
+        assert_eq!(board.solve(), Ok(()));
+        assert_eq!(board, expected);
+        assert!(board.is_solved());
+
+        // A wrong entry makes the board unsolvable; it is left exactly as it was.
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.try_set((0, 0), 2).unwrap();
+        let before = board;
+        assert_eq!(board.solve(), Err(SudokuError::Unsolvable));
+        assert_eq!(board, before);
+
+        board.internal_place((0, 1), 2);
+        let before = board;
+        assert_eq!(board.solve(), Err(SudokuError::InvalidConfig));
+        assert_eq!(board, before);
+    }
+
+    #[test]
+    fn test_is_consistent() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.try_set((0, 0), 3).unwrap();
+        assert!(board.is_consistent());
+        assert!(!board.is_solved());
+
+        // A duplicate 3 in row 0, entered past the checks as a careless player might.
+        board.internal_place((0, 1), 3);
+        assert!(!board.is_consistent());
+        board.clear((0, 1)).unwrap();
+        assert!(board.is_consistent());
+    }
+
+    // Whether the stored masks match masks rebuilt from the grid.
+    fn masks_in_sync(board: &SudokuBoard) -> bool {
+        let mut rebuilt = *board;
+        rebuilt.recompute_masks();
+        board.row_masks == rebuilt.row_masks
+            && board.col_masks == rebuilt.col_masks
+            && board.box_masks == rebuilt.box_masks
+    }
+
+    #[test]
+    fn test_masks_stay_consistent() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        assert!(masks_in_sync(&board));
+        assert_eq!(board.row_masks[0], (1 << 4) | (1 << 6) | (1 << 7) | (1 << 9));
+
+        let moves = [((0, 0), 3), ((0, 1), 8), ((2, 0), 5), ((0, 0), 0), ((1, 0), 2), ((0, 1), 0), ((0, 0), 2)];
+        for (cell, num) in moves {
+            let _ = board.try_place(cell, num);
+            assert!(masks_in_sync(&board));
+        }
+        board.swap_columns(0, 1).unwrap();
+        assert!(masks_in_sync(&board));
+        board.relabel_inplace([9, 8, 7, 6, 5, 4, 3, 2, 1]).unwrap();
+        assert!(masks_in_sync(&board));
+        assert!(masks_in_sync(&board.givens_only()));
+
+        // The O(1) check agrees with the candidates computed from the peers.
+        for r in 0..9 {
+            for c in 0..9 {
+                for num in 1..=9 {
+                    let expected = board.peer_value_mask((r, c)).unwrap() & (1 << num) == 0;
+                    assert_eq!(board.is_placement_valid((r, c), num), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_unchecked_matches_get() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        for r in 0..9 {
+            for c in 0..9 {
+                // SAFETY: r and c are both below 9.
+                let value = unsafe { board.get_unchecked(r, c) };
+                assert_eq!(Some(value), board.get((r as u8, c as u8)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_as_flat() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        let flat = board.as_flat();
+        for r in 0..9 {
+            for c in 0..9 {
+                assert_eq!(Some(flat[r * 9 + c]), board.get((r as u8, c as u8)));
+            }
+        }
+        // The view borrows the board's own storage.
+        assert_eq!(flat.as_ptr(), board.get_board().as_ptr() as *const u8);
+    }
+
+    #[test]
+    fn test_iter_cells() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        let cells: Vec<_> = board.iter_cells().collect();
+        assert_eq!(cells.len(), 81);
+        assert_eq!(cells[0], ((0, 0), 0));
+        assert_eq!(cells[2], ((0, 2), 6));
+        assert_eq!(cells[80], ((8, 8), 0));
+        assert!(cells.iter().all(|&(cell, value)| board.get(cell) == Some(value)));
+
+        let empties = board.iter_cells().filter(|(_, v)| *v == 0).count();
+        assert_eq!(empties, 81 - 37);
+        assert_eq!(board.iter_empty().count(), empties);
+        assert_eq!(board.iter_empty().next(), Some((0, 0)));
+        assert!(board.iter_empty().all(|cell| board.get(cell) == Some(0)));
+    }
+
+    #[test]
+    fn test_try_place_scenarios() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+
+        // --- Success Cases ---
+        // Placing a valid number on an empty cell.
+        assert!(board.try_place((0, 0), 2).is_ok());
+        assert_eq!(board.get((0, 0)), Some(2));
+        
+        // Clearing a placed number.
+        assert!(board.try_place((0, 0), 0).is_ok());
+        assert_eq!(board.get((0, 0)), Some(0));
+
+        // --- Failure Cases ---
+        // Overwriting a placed number with non-0 number
+        board.try_place((0, 0), 3).unwrap();
+        assert!(board.try_place((0, 0), 4).is_err());
+
+        // Re-sending the value already in the cell is an idempotent success.
+        assert!(board.try_place((0, 0), 3).is_ok());
+        assert_eq!(board.get((0, 0)), Some(3));
+
+        board.try_place((0, 0), 0).unwrap(); // clear placed number from previous test.
+
+        // Trying to place on an initial number.
+        assert!(board.try_place((0, 2), 5).is_err());
+
+        // Trying to place out of bounds.
+        assert!(board.try_place((9, 9), 5).is_err());
+
+        // Trying to place an invalid number.
+        assert!(board.try_place((0, 0), 10).is_err());
+
+        // Trying to place a number that conflicts with a row.
+        assert!(board.try_place((0, 0), 9).is_err());
 
         // Try to place a number that conflicts with a column.
         assert!(board.try_place((0, 0), 1).is_err());
@@ -304,4 +2107,521 @@ mod tests {
         // Try to place a number that conflicts with a box.
         assert!(board.try_place((0, 0), 4).is_err());
     }
+
+    #[test]
+    fn test_place_and_unplace_fast() {
+        let original = SudokuBoard::from(valid_config()).unwrap();
+        let mut board = original;
+
+        board.place_fast((0, 0), 2);
+        assert_eq!(board.get((0, 0)), Some(2));
+
+        board.unplace_fast((0, 0), 2);
+        assert_eq!(board.get_board(), original.get_board());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "starting number")]
+    fn test_place_fast_on_starting_number_panics_in_debug() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.place_fast((0, 2), 5);
+    }
+
+    #[test]
+    fn test_digit_counts() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.digit_counts(), [5, 3, 4, 2, 3, 7, 5, 3, 5]);
+
+        let solved = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        assert_eq!(solved.digit_counts(), [9; 9]);
+        assert_eq!(SudokuBoard::from([[0; 9]; 9]).unwrap().digit_counts(), [0; 9]);
+    }
+
+    #[test]
+    fn test_is_complete() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        assert!(!board.is_complete());
+
+        let mut solved = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        assert!(solved.is_complete());
+
+        // A single empty cell makes it incomplete again.
+        solved.internal_place((0, 0), 0);
+        assert!(!solved.is_complete());
+
+        // Completeness alone doesn't check the rules.
+        let mut broken = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        broken.internal_place((0, 0), 6);
+        assert!(broken.is_complete());
+        assert!(!SudokuBoard::is_valid_config(broken.get_board()));
+    }
+
+    #[test]
+    fn test_is_solved() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        assert!(!board.is_solved());
+
+        let mut solved = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        assert!(solved.is_solved());
+
+        // A valid board with one empty cell isn't solved.
+        let mut almost = solved;
+        almost.internal_place((0, 0), 0);
+        assert!(!almost.is_solved());
+
+        // Neither is a full board that breaks the rules.
+        solved.internal_place((0, 0), 6);
+        assert!(!solved.is_solved());
+    }
+
+    #[test]
+    fn test_grade_against() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        let solution = crate::sudoku_solver::BacktrackingSolver::solution(&board).unwrap();
+        let blanks = valid_config().iter().flatten().filter(|&&v| v == 0).count();
+
+        // Nothing has been entered yet.
+        assert_eq!(board.grade_against(&solution), Grade { correct: 0, incorrect: 0, empty: blanks });
+
+        // (0, 0) is 3 in the solution; (0, 3) is 5, so 1 is a legal but wrong entry.
+        board.try_place((0, 0), 3).unwrap();
+        board.try_place((0, 3), 1).unwrap();
+        assert_eq!(board.grade_against(&solution), Grade { correct: 1, incorrect: 1, empty: blanks - 2 });
+
+        // A solved board is fully correct.
+        assert_eq!(solution.as_board().grade_against(&solution), Grade { correct: blanks, incorrect: 0, empty: 0 });
+    }
+
+    #[test]
+    fn test_below_minimum_clues() {
+        // The sample config has 37 clues.
+        assert!(!SudokuBoard::from(valid_config()).unwrap().below_minimum_clues());
+
+        // Player entries are not clues.
+        let mut board = SudokuBoard::from([[0; 9]; 9]).unwrap();
+        board.try_place((0, 0), 1).unwrap();
+        assert!(board.below_minimum_clues());
+    }
+
+    #[test]
+    fn test_freeze() {
+        let mut board = SudokuBoard::empty();
+        board.try_set((0, 0), 5).unwrap();
+        board.try_set((4, 4), 7).unwrap();
+        board.freeze();
+
+        assert_eq!(board.given_count(), 2);
+        assert_eq!(board.try_set((0, 0), 1), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(board.try_set((4, 4), 0), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(board.clear((0, 0)), Err(SudokuError::CannotModifyInitial));
+        assert_eq!(board.try_set((0, 1), 1), Ok(()));
+        assert_eq!(board.try_set((0, 1), 0), Ok(()));
+
+        // Freezing a loaded puzzle changes nothing.
+        let mut puzzle = SudokuBoard::from(valid_config()).unwrap();
+        puzzle.freeze();
+        assert_eq!(puzzle, SudokuBoard::from(valid_config()).unwrap());
+    }
+
+    #[test]
+    fn test_set_given() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+
+        // Placing a new given locks it against further edits.
+        assert!(board.set_given((0, 0), 3).is_ok());
+        assert_eq!(board.get((0, 0)), Some(3));
+        assert!(board.try_place((0, 0), 0).is_err());
+
+        // An existing entry can be promoted.
+        board.try_place((0, 1), 8).unwrap();
+        assert!(board.set_given((0, 1), 8).is_ok());
+        assert!(board.try_place((0, 1), 0).is_err());
+
+        // Conflicts, overwrites, zero, and out of bounds are rejected.
+        assert!(board.set_given((0, 3), 9).is_err());
+        board.try_place((0, 3), 5).unwrap();
+        assert!(board.set_given((0, 3), 1).is_err());
+        assert!(board.set_given((1, 0), 0).is_err());
+        assert!(board.set_given((9, 0), 1).is_err());
+    }
+
+    #[test]
+    fn test_swap_columns() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.try_place((0, 0), 3).unwrap();
+
+        // Columns 0 and 2 share the first stack.
+        assert!(board.swap_columns(0, 2).is_ok());
+        assert!(SudokuBoard::is_valid_config(board.get_board()));
+        assert_eq!(board.get((0, 0)), Some(6));
+        assert_eq!(board.get((0, 2)), Some(3));
+
+        // The starting number moved with its value, and so did the player entry.
+        assert!(board.try_place((0, 0), 0).is_err());
+        assert!(board.try_place((0, 2), 0).is_ok());
+
+        // Columns in different stacks, or off the board, are rejected untouched.
+        let before = *board.get_board();
+        assert!(board.swap_columns(2, 3).is_err());
+        assert!(board.swap_columns(0, 9).is_err());
+        assert_eq!(board.get_board(), &before);
+    }
+
+    #[test]
+    fn test_rotations_and_reflections() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+        board.try_set((0, 0), 3).unwrap();
+
+        let rotated = board.rotate90();
+        assert_eq!(rotated.get((0, 8)), Some(3));
+        assert_eq!(rotated.is_given((0, 8)), Some(false));
+        assert_eq!(rotated.get((2, 8)), Some(6));
+        assert_eq!(rotated.is_given((2, 8)), Some(true));
+        assert!(masks_in_sync(&rotated));
+        assert_ne!(rotated, board);
+        assert_eq!(rotated.rotate90().rotate90().rotate90(), board);
+
+        let transposed = board.transpose();
+        assert_eq!(transposed.get((2, 0)), Some(6));
+        assert_eq!(transposed.transpose(), board);
+
+        let mirrored = board.mirror_horizontal();
+        assert_eq!(mirrored.get((8, 0)), Some(3));
+        assert_eq!(mirrored.row(0), board.row(8));
+        assert_eq!(mirrored.mirror_horizontal(), board);
+
+        let solved = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        for transformed in [solved.rotate90(), solved.transpose(), solved.mirror_horizontal()] {
+            assert!(transformed.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_relabel_inplace() {
+        let original = SudokuBoard::from(valid_config()).unwrap();
+        let mut board = original;
+
+        // Swap 1s and 2s, then swap them back.
+        let swap = [2, 1, 3, 4, 5, 6, 7, 8, 9];
+        assert!(board.relabel_inplace(swap).is_ok());
+        assert_eq!(board.get((2, 1)), Some(2));
+        assert_eq!(board.get((5, 1)), Some(1));
+        assert!(SudokuBoard::is_valid_config(board.get_board()));
+        assert!(board.try_place((2, 1), 0).is_err()); // still a starting number
+        board.relabel_inplace(swap).unwrap();
+        assert_eq!(board.get_board(), original.get_board());
+
+        // Non-permutations are rejected and leave the board untouched.
+        assert!(board.relabel_inplace([1, 1, 3, 4, 5, 6, 7, 8, 9]).is_err());
+        assert!(board.relabel_inplace([0, 2, 3, 4, 5, 6, 7, 8, 9]).is_err());
+        assert!(board.relabel_inplace([10, 2, 3, 4, 5, 6, 7, 8, 9]).is_err());
+        assert_eq!(board.get_board(), original.get_board());
+    }
+
+    #[test]
+    fn test_canonical() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        let canonical = board.canonical();
+        assert_eq!(canonical.canonical(), canonical);
+        assert_eq!(canonical.given_count(), board.given_count());
+        assert!(canonical.is_consistent());
+        assert!(canonical.to_line() <= board.to_line());
+
+        // Bands 0 and 2 swapped, then turned, mirrored and renamed: still the same puzzle.
+        let swapped = board.remapped(|r, c| (6 - r / 3 * 3 + r % 3, c));
+        let variant = swapped.rotate90().mirror_horizontal().relabel([5, 3, 9, 1, 2, 8, 6, 7, 4]).unwrap();
+        assert_ne!(variant.to_line(), board.to_line());
+        assert_eq!(variant.canonical(), canonical);
+        assert_eq!(board.transpose().canonical(), canonical);
+
+        // A different puzzle has a different canonical form.
+        let mut other = board;
+        other.set_given((0, 0), 3).unwrap();
+        assert_ne!(other.canonical(), canonical);
+    }
+
+    #[test]
+    fn test_relabel() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.relabel([1, 2, 3, 4, 5, 6, 7, 8, 9]), Ok(board));
+
+        let reversed = board.relabel([9, 8, 7, 6, 5, 4, 3, 2, 1]).unwrap();
+        assert_eq!(reversed.get((0, 2)), Some(4));
+        assert_eq!(reversed.is_given((0, 2)), Some(true));
+        assert!(reversed.is_consistent());
+        assert!(masks_in_sync(&reversed));
+        assert_eq!(board.get((0, 2)), Some(6));
+
+        assert_eq!(board.relabel([1, 1, 3, 4, 5, 6, 7, 8, 9]), Err(SudokuError::InvalidMapping));
+        assert_eq!(board.relabel([0, 2, 3, 4, 5, 6, 7, 8, 9]), Err(SudokuError::InvalidMapping));
+    }
+
+    #[test]
+    fn test_try_place_tracked() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+
+        // Placing 3 at (0, 0) erases 3 from exactly the peers that could hold it.
+        let eliminated = board.try_place_tracked((0, 0), 3).unwrap();
+        assert_eq!(eliminated, vec![((0, 1), 3), ((5, 0), 3), ((6, 0), 3), ((7, 0), 3)]);
+        for &(peer, num) in &eliminated {
+            assert!(!board.is_placement_valid(peer, num));
+        }
+
+        // A rejected move reports an error and leaves the board as it was.
+        let before = *board.get_board();
+        assert!(board.try_place_tracked((0, 1), 3).is_err());
+        assert_eq!(board.get_board(), &before);
+
+        // Clearing erases nothing.
+        assert_eq!(board.try_place_tracked((0, 0), 0).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_try_place_tracked_rejects_bad_input() {
+        let mut board = SudokuBoard::from([[0; 9]; 9]).unwrap();
+        assert!(board.try_place_tracked((0, 0), 200).is_err());
+        assert!(board.try_place_tracked((9, 0), 1).is_err());
+        assert_eq!(board.get_board(), &[[0; 9]; 9]);
+    }
+
+    #[test]
+    fn test_place_and_forced() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+
+        // (0, 1) has candidates {3, 8}; placing 3 at (0, 0) leaves only 8.
+        let forced = board.place_and_forced((0, 0), 3).unwrap();
+        assert!(forced.contains(&((0, 1), 8)));
+        for &(cell, value) in &forced {
+            assert_eq!(board.candidate_count(cell), Some(1));
+            assert!(board.is_placement_valid(cell, value));
+        }
+
+        // A rejected placement reports the error and leaves the board alone.
+        let before = *board.get_board();
+        assert!(board.place_and_forced((0, 2), 5).is_err());
+        assert_eq!(board.get_board(), &before);
+    }
+
+    #[test]
+    fn test_units() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        let units: Vec<_> = board.units().collect();
+        assert_eq!(units.len(), 27);
+
+        assert_eq!(units[0].0, UnitKind::Row);
+        assert_eq!(units[9 + 4], (UnitKind::Column, 4, std::array::from_fn(|i| (i as u8, 4))));
+        let box_4 = units[18 + 4];
+        assert_eq!(box_4.0, UnitKind::Box);
+        assert_eq!(box_4.2[0], (3, 3));
+        assert_eq!(box_4.2[8], (5, 5));
+
+        // Every cell belongs to exactly one row, one column and one box.
+        let mut seen = [[0; 9]; 9];
+        for (_, _, cells) in units {
+            for (r, c) in cells {
+                seen[r as usize][c as usize] += 1;
+            }
+        }
+        assert!(seen.iter().flatten().all(|&count| count == 3));
+    }
+
+    #[test]
+    fn test_candidates_restored_after_clear() {
+        let mut board = SudokuBoard::from(valid_config()).unwrap();
+
+        // (0, 1) starts with candidates {3, 8}.
+        assert_eq!(board.candidate_count((0, 1)), Some(2));
+
+        // Filling its peer (0, 0) with 3 takes that candidate away...
+        board.try_place((0, 0), 3).unwrap();
+        assert_eq!(board.candidate_count((0, 1)), Some(1));
+        assert!(!board.is_placement_valid((0, 1), 3));
+
+        // ...and clearing the peer gives it back.
+        board.try_place((0, 0), 0).unwrap();
+        assert_eq!(board.candidate_count((0, 1)), Some(2));
+        assert!(board.is_placement_valid((0, 1), 3));
+
+        // Derived queries see the restored state as well.
+        assert_eq!(board.cell_by_candidate_count(false), Some((2, 0)));
+        assert_eq!(board.place_and_forced((0, 0), 3).unwrap().first(), Some(&((0, 1), 8)));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_print_lines_are_aligned() {
+        let rendered = SudokuBoard::from(valid_config()).unwrap().to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 15);
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+        assert_eq!(lines[1], "|     CURRENT BOARD STATE     |");
+        assert_eq!(lines[3], "| 0  0  6 | 0  4  0 | 0  9  7 |");
+        assert!(!rendered.ends_with('\n'));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_diff_string() {
+        let original = SudokuBoard::from(valid_config()).unwrap();
+        let mut played = original;
+        played.try_place((0, 0), 3).unwrap();
+
+        let diff = original.diff_string(&played);
+        let lines: Vec<&str> = diff.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+
+        // Only (0, 0) differs, and it is marked on both sides.
+        assert_eq!(lines[1], "|[0] 0  6 | 0  4  0 | 0  9  7 |   |[3] 0  6 | 0  4  0 | 0  9  7 |");
+        assert_eq!(diff.matches('[').count(), 2);
+
+        // Identical boards have nothing marked.
+        assert!(!original.diff_string(&original).contains('['));
+    }
+
+    #[test]
+    fn test_peer_value_mask() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+
+        // Peers of (0, 0) hold 1, 4, 6, 7, 8 and 9, leaving candidates 2, 3 and 5.
+        let mask = board.peer_value_mask((0, 0)).unwrap();
+        assert_eq!(mask, 0b11_1101_0010);
+        assert_eq!(!mask & 0x3FE, (1 << 2) | (1 << 3) | (1 << 5));
+
+        // A cell's own value is not part of its peer mask.
+        assert_eq!(board.peer_value_mask((0, 2)).unwrap() & (1 << 6), 0);
+
+        assert_eq!(board.peer_value_mask((9, 0)), None);
+        assert_eq!(board.peer_value_mask((0, 9)), None);
+    }
+
+    #[test]
+    fn test_candidates() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.candidates((0, 0)), vec![2, 3, 5]);
+        assert_eq!(board.candidates_mask((0, 0)), (1 << 2) | (1 << 3) | (1 << 5));
+        assert_eq!(board.candidates((2, 0)), vec![5]);
+
+        // Filled and out-of-bounds cells have no candidates.
+        assert!(board.candidates((0, 2)).is_empty());
+        assert_eq!(board.candidates_mask((0, 2)), 0);
+        assert!(board.candidates((9, 0)).is_empty());
+        assert_eq!(board.candidates_mask((0, 9)), 0);
+
+        // Every candidate is a legal move.
+        let mut scratch = board;
+        for num in board.candidates((1, 6)) {
+            assert!(scratch.try_place((1, 6), num).is_ok());
+            scratch.try_place((1, 6), 0).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_candidate_count() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        for r in 0..9 {
+            for c in 0..9 {
+                let legal = (1..=9).filter(|&num| board.is_placement_valid((r, c), num)).count();
+                let expected = if board.get((r, c)) == Some(0) { legal as u8 } else { 0 };
+                assert_eq!(board.candidate_count((r, c)), Some(expected));
+                assert_eq!(board.candidates((r, c)).len(), expected as usize);
+            }
+        }
+        assert_eq!(board.candidate_count((0, 0)), Some(3));
+        assert_eq!(board.candidate_count((0, 2)), Some(0));
+        assert_eq!(board.candidate_count((9, 0)), None);
+    }
+
+    #[test]
+    fn test_peers() {
+        let peers = SudokuBoard::peers((4, 4)).unwrap();
+        assert_eq!(peers.len(), 20);
+        assert!(!peers.contains(&(4, 4)));
+        assert_eq!(peers.iter().collect::<HashSet<_>>().len(), 20);
+        assert!(peers.contains(&(4, 0)) && peers.contains(&(0, 4)) && peers.contains(&(3, 5)));
+        assert!(!peers.contains(&(3, 6)));
+
+        for r in 0..9 {
+            for c in 0..9 {
+                assert_eq!(SudokuBoard::peers((r, c)).unwrap().len(), 20);
+            }
+        }
+        assert_eq!(SudokuBoard::peers((0, 9)), None);
+    }
+
+    #[test]
+    fn test_box_index_and_origin() {
+        assert_eq!(SudokuBoard::box_index((0, 0)), Some(0));
+        assert_eq!(SudokuBoard::box_index((1, 5)), Some(1));
+        assert_eq!(SudokuBoard::box_index((4, 4)), Some(4));
+        assert_eq!(SudokuBoard::box_index((6, 2)), Some(6));
+        assert_eq!(SudokuBoard::box_index((8, 8)), Some(8));
+        assert_eq!(SudokuBoard::box_index((9, 0)), None);
+
+        assert_eq!(SudokuBoard::box_origin(0), Some((0, 0)));
+        assert_eq!(SudokuBoard::box_origin(5), Some((3, 6)));
+        assert_eq!(SudokuBoard::box_origin(7), Some((6, 3)));
+        assert_eq!(SudokuBoard::box_origin(9), None);
+
+        // Every cell lies within three rows and columns of its box's origin.
+        for r in 0..9 {
+            for c in 0..9 {
+                let (or, oc) = SudokuBoard::box_origin(SudokuBoard::box_index((r, c)).unwrap()).unwrap();
+                assert!((or..or + 3).contains(&r) && (oc..oc + 3).contains(&c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_unit_accessors() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        assert_eq!(board.row(0), Some([0, 0, 6, 0, 4, 0, 0, 9, 7]));
+        assert_eq!(board.row(8), Some([8, 6, 0, 0, 2, 0, 3, 0, 0]));
+        assert_eq!(board.col(0), Some([0, 0, 0, 6, 1, 0, 0, 0, 8]));
+        assert_eq!(board.box_cells(0), Some([0, 0, 6, 0, 4, 0, 0, 1, 7]));
+        assert_eq!(board.box_cells(4), Some([0, 7, 0, 0, 6, 0, 0, 1, 0]));
+        assert_eq!(board.box_cells(8), Some([1, 6, 0, 0, 7, 0, 3, 0, 0]));
+
+        assert_eq!(board.row(9), None);
+        assert_eq!(board.col(9), None);
+        assert_eq!(board.box_cells(9), None);
+    }
+
+    #[test]
+    fn test_hidden_single_in_unit() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+
+        // In box 1, 6 can only go in (2, 3); box 0 has no hidden single.
+        assert_eq!(board.hidden_single_in_unit(UnitKind::Box, 1), Some(((2, 3), 6)));
+        assert_eq!(board.hidden_single_in_unit(UnitKind::Row, 4), Some(((4, 1), 7)));
+        assert_eq!(board.hidden_single_in_unit(UnitKind::Box, 0), None);
+
+        // Every result is an empty cell where the digit is legal.
+        for (kind, index, _) in board.units() {
+            if let Some((cell, num)) = board.hidden_single_in_unit(kind, index) {
+                assert_eq!(board.get(cell), Some(0));
+                assert!(board.is_placement_valid(cell, num));
+            }
+        }
+
+        // A solved board has nothing left to find; bad indices return None.
+        let solved = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        assert_eq!(solved.hidden_single_in_unit(UnitKind::Row, 0), None);
+        assert_eq!(board.hidden_single_in_unit(UnitKind::Column, 9), None);
+    }
+
+    #[test]
+    fn test_cell_by_candidate_count() {
+        let board = SudokuBoard::from(valid_config()).unwrap();
+        // (2, 0) is the first cell with a single candidate.
+        assert_eq!(board.cell_by_candidate_count(false), Some((2, 0)));
+        // (5, 5) is the only cell with five candidates.
+        assert_eq!(board.cell_by_candidate_count(true), Some((5, 5)));
+
+        // A full board has no empty cells to choose from.
+        let solved = crate::sudoku_solver::BacktrackingSolver::run(&board).unwrap();
+        assert_eq!(solved.cell_by_candidate_count(true), None);
+        assert_eq!(solved.cell_by_candidate_count(false), None);
+    }
 }
@@ -1,66 +1,1214 @@
+#[cfg(feature = "extra_solvers")]
+use crate::candidate_grid::CandidateGrid;
+use crate::rng::Rng;
+#[cfg(feature = "extra_solvers")]
+use crate::sized_board::Board9;
+use crate::sized_board::SizedBoard;
 use crate::sudoku_board::SudokuBoard;
+#[cfg(feature = "extra_solvers")]
+use crate::sudoku_board::{Placement, UnitKind};
+#[cfg(feature = "extra_solvers")]
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::sync::mpsc;
+#[cfg(feature = "std")]
+use std::thread;
 
 pub struct BacktrackingSolver;
 
+/// How much work a backtracking solve took.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Numbers placed, including ones later taken back.
+    pub nodes: u32,
+    /// Cells branched on that had more than one candidate.
+    pub guesses: u32,
+    /// Numbers taken back after leading to a dead end.
+    pub backtracks: u32,
+    /// The most numbers placed on the board by the search at any one time.
+    pub max_depth: u32,
+}
+
+/// A completely and correctly filled board. Only the solvers construct these, so APIs
+/// taking a `&Solution` can't be handed a partial board by mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solution(SudokuBoard);
+
+impl Solution {
+    /// Borrows the solved board.
+    pub fn as_board(&self) -> &SudokuBoard {
+        &self.0
+    }
+
+    /// Unwraps the solved board, e.g. to keep playing on it.
+    pub fn into_board(self) -> SudokuBoard {
+        self.0
+    }
+}
+
+/// Reasons a solve was stopped before the search could finish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// The board already breaks a Sudoku rule, so no search was attempted.
+    InconsistentBoard,
+    /// The starting numbers can be completed, but not with the player's entries.
+    ContradictsEntries,
+    /// The starting numbers alone already have no solution.
+    UnsolvableGivens,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::InconsistentBoard => write!(f, "Error: Board breaks a Sudoku rule and cannot be solved."),
+            SolveError::ContradictsEntries => write!(f, "Error: The entries made so far lead to no solution."),
+            SolveError::UnsolvableGivens => write!(f, "Error: The puzzle has no solution."),
+        }
+    }
+}
+
+impl core::error::Error for SolveError {}
+
+/// The result of a solve, separating an unsolvable puzzle from an aborted search.
+// Boards are returned by value everywhere else too; boxing would cost an allocation per solve.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveOutcome {
+    Solved(SudokuBoard),
+    Unsolvable,
+    Aborted(SolveError),
+}
+
 impl BacktrackingSolver {
-    /// The recursive helper function that implements the backtracking logic.
+    /// Solves the board with depth-first backtracking, returning None if it is unsolvable.
+    ///
+    /// The search is deterministic: it always branches on the empty cell with the fewest
+    /// candidates (ties broken in row-major order) and tries values in ascending order, so
+    /// a board with several solutions always yields the same one.
     pub fn run(init_board: &SudokuBoard) -> Option<SudokuBoard> {
-        // returns false if unsolvable.
+        match Self::run_outcome(init_board) {
+            SolveOutcome::Solved(board) => Some(board),
+            SolveOutcome::Unsolvable | SolveOutcome::Aborted(_) => None,
+        }
+    }
+
+    /// Like `run`, but tries each cell's numbers in an order shuffled by a generator seeded
+    /// with `seed`, so a board with many solutions yields different ones for different seeds.
+    /// The same board and seed always give the same result.
+    pub fn run_random(init_board: &SudokuBoard, seed: u64) -> Option<SudokuBoard> {
+        if !init_board.is_consistent() {
+            return None;
+        }
         let mut board = *init_board;
-        if Self::recursive_solve(&mut board) {
+        Self::random_solve(&mut board, &mut Rng::new(seed)).then_some(board)
+    }
+
+    fn random_solve(board: &mut SudokuBoard, rng: &mut Rng) -> bool {
+        let Some(empty_cell) = Self::find_most_constrained_cell(board) else {
+            return true; // No empty cells, board is solved
+        };
+        let mut nums = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        rng.shuffle(&mut nums);
+        for num in nums {
+            if board.is_placement_valid(empty_cell, num) {
+                board.place_fast(empty_cell, num);
+                if Self::random_solve(board, rng) {
+                    return true;
+                }
+                board.unplace_fast(empty_cell, num);
+            }
+        }
+        false
+    }
+
+    /// Solves a board of any size the same way `run` does, returning None if it is unsolvable.
+    pub fn run_sized<const B: usize>(init_board: &SizedBoard<B>) -> Option<SizedBoard<B>> {
+        if !SizedBoard::<B>::is_valid_config(init_board.as_flat()) {
+            return None;
+        }
+        let mut board = init_board.clone();
+        Self::recursive_solve_sized(&mut board).then_some(board)
+    }
+
+    fn recursive_solve_sized<const B: usize>(board: &mut SizedBoard<B>) -> bool {
+        let Some((cell, candidates)) = board.most_constrained_cell() else {
+            return true; // No empty cells, board is solved
+        };
+        for num in (1..=SizedBoard::<B>::SIDE as u8).filter(|&num| candidates & (1 << num) != 0) {
+            board.internal_place(cell, num);
+            if Self::recursive_solve_sized(board) {
+                return true;
+            }
+        }
+        board.internal_place(cell, 0);
+        false
+    }
+
+    /// Like `run`, but wraps the result in a `Solution`.
+    pub fn solution(init_board: &SudokuBoard) -> Option<Solution> {
+        Self::run(init_board).map(Solution)
+    }
+
+    /// Like `run`, but reports whether a failed solve was unsolvable or aborted.
+    pub fn run_outcome(init_board: &SudokuBoard) -> SolveOutcome {
+        // Boards filled through the unchecked primitives may already be inconsistent.
+        if !init_board.is_consistent() {
+            return SolveOutcome::Aborted(SolveError::InconsistentBoard);
+        }
+
+        let mut board = *init_board;
+        if Self::search(&mut board, &mut SolveStats::default()) {
             // board is guaranteed to be valid if solved by properties of SudokuBoard
-            return Some(board);
+            return SolveOutcome::Solved(board);
+        }
+        SolveOutcome::Unsolvable
+    }
+
+    /// Completes a partially played board, keeping every filled cell (starting numbers and
+    /// player entries alike) fixed. `run` does the same, but on failure `finish` also tells
+    /// a wrong player entry (`ContradictsEntries`) apart from an unsolvable puzzle
+    /// (`UnsolvableGivens`).
+    pub fn finish(board: &SudokuBoard) -> Result<SudokuBoard, SolveError> {
+        match Self::run_outcome(board) {
+            SolveOutcome::Solved(solved) => Ok(solved),
+            SolveOutcome::Aborted(err) => Err(err),
+            SolveOutcome::Unsolvable => {
+                if Self::run(&board.givens_only()).is_some() {
+                    Err(SolveError::ContradictsEntries)
+                } else {
+                    Err(SolveError::UnsolvableGivens)
+                }
+            }
+        }
+    }
+
+    /// Counts the distinct solutions of a board, stopping as soon as `limit` is reached.
+    /// Pass a limit of 2 to cheaply check that a puzzle has exactly one solution. Boards
+    /// that already break a rule have no solutions.
+    pub fn count_solutions(board: &SudokuBoard, limit: usize) -> usize {
+        if limit == 0 || !board.is_consistent() {
+            return 0;
+        }
+        let mut board = *board;
+        let mut count = 0;
+        Self::recursive_count(&mut board, limit, &mut count);
+        count
+    }
+
+    fn recursive_count(board: &mut SudokuBoard, limit: usize, count: &mut usize) {
+        let Some(empty_cell) = Self::find_most_constrained_cell(board) else {
+            *count += 1; // No empty cells, found a solution
+            return;
+        };
+        for num in 1..=9 {
+            if board.is_placement_valid(empty_cell, num) {
+                board.place_fast(empty_cell, num);
+                Self::recursive_count(board, limit, count);
+                board.unplace_fast(empty_cell, num);
+                if *count >= limit {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// A quick difficulty estimate for bulk triage: the number of guesses (cells tried with
+    /// more than one candidate) plus the number of backtracks the solver needs.
+    ///
+    /// This is a heuristic that only correlates with how hard a puzzle is for a human; use it
+    /// to pre-filter puzzles, not as a rating. Unsolvable boards score the effort to prove it.
+    pub fn difficulty_proxy(board: &SudokuBoard) -> u32 {
+        let mut board = *board;
+        let mut stats = SolveStats::default();
+        Self::search(&mut board, &mut stats);
+        stats.guesses.saturating_add(stats.backtracks)
+    }
+
+    /// Like `run`, but also reports how much work the search took.
+    pub fn run_with_stats(init_board: &SudokuBoard) -> (Option<SudokuBoard>, SolveStats) {
+        let mut stats = SolveStats::default();
+        if !init_board.is_consistent() {
+            return (None, stats);
+        }
+        let mut board = *init_board;
+        let solved = Self::search(&mut board, &mut stats);
+        (solved.then_some(board), stats)
+    }
+
+    /// Like `run`, but searches the branches of the first cell it branches on in parallel,
+    /// one thread per candidate, and returns whichever solution is found first; the other
+    /// threads are then told to stop. A puzzle with one solution gets the same answer as
+    /// `run`, but on a board with several solutions which one is returned is not
+    /// deterministic. Needs the `std` feature for threads.
+    #[cfg(feature = "std")]
+    pub fn run_parallel(init_board: &SudokuBoard) -> Option<SudokuBoard> {
+        if !init_board.is_consistent() {
+            return None;
+        }
+        let Some(cell) = Self::find_most_constrained_cell(init_board) else {
+            return Some(*init_board); // Already full, and consistent
+        };
+
+        let stop = AtomicBool::new(false);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for num in init_board.candidates(cell) {
+                let (stop, sender) = (&stop, sender.clone());
+                scope.spawn(move || {
+                    let mut board = *init_board;
+                    board.place_fast(cell, num);
+                    if Self::search_until(&mut board, &mut SolveStats::default(), stop) {
+                        stop.store(true, Ordering::Relaxed);
+                        // The receiver outlives the scope, so this can't fail.
+                        let _ = sender.send(board);
+                    }
+                });
+            }
+        });
+        receiver.try_recv().ok()
+    }
+
+    // Depth-first search with an explicit stack of (cell, number placed there) frames, where
+    // 0 means nothing has been tried yet. Leaves the solution on `board` and returns true,
+    // or restores `board` and returns false.
+    fn search(board: &mut SudokuBoard, stats: &mut SolveStats) -> bool {
+        Self::search_until(board, stats, &AtomicBool::new(false))
+    }
+
+    // Like `search`, but gives up and returns false, leaving `board` partly filled, as soon
+    // as `stop` is set.
+    fn search_until(board: &mut SudokuBoard, stats: &mut SolveStats, stop: &AtomicBool) -> bool {
+        let mut stack: Vec<((u8, u8), u8)> = Vec::with_capacity(81);
+        let mut descend = true;
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                return false;
+            }
+            if descend {
+                stats.max_depth = stats.max_depth.max(stack.len() as u32);
+                let Some(empty_cell) = Self::find_most_constrained_cell(board) else {
+                    return true; // No empty cells, board is solved
+                };
+                if (1..=9).filter(|&num| board.is_placement_valid(empty_cell, num)).count() > 1 {
+                    stats.guesses += 1;
+                }
+                stack.push((empty_cell, 0));
+            }
+
+            // Move the deepest frame on to its next number, or drop it if none is left.
+            let Some(&(cell, current)) = stack.last() else {
+                return false; // Every option failed, board is unsolvable
+            };
+            if current != 0 {
+                board.unplace_fast(cell, current);
+                stats.backtracks += 1;
+            }
+            match (current + 1..=9).find(|&num| board.is_placement_valid(cell, num)) {
+                Some(num) => {
+                    board.place_fast(cell, num);
+                    stats.nodes += 1;
+                    stack.last_mut().unwrap().1 = num;
+                    descend = true;
+                }
+                None => {
+                    stack.pop();
+                    descend = false;
+                }
+            }
+        }
+    }
+
+    // Picks the empty cell with the fewest candidates (minimum remaining values), so forced
+    // cells are filled first and dead ends are found early. Ties go to the first cell in
+    // row-major order. Returns None if the board has no empty cells.
+    fn find_most_constrained_cell(board: &SudokuBoard) -> Option<(u8, u8)> {
+        board.cell_by_candidate_count(false)
+    }
+}
+
+/// One action taken by `StepSolver`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverStep {
+    /// `num` was placed at `cell`.
+    Place { cell: (u8, u8), num: u8 },
+    /// The number at `cell` led to a dead end and was cleared.
+    Backtrack { cell: (u8, u8) },
+}
+
+/// Runs the same search as `BacktrackingSolver` one placement or backtrack at a time, so
+/// front-ends can animate it. Once `step` returns None, `board` is either solved or back to
+/// where it started, if there is no solution.
+pub struct StepSolver {
+    board: SudokuBoard,
+    // Branch cells and the number last tried in each (0 before the first try).
+    stack: Vec<((u8, u8), u8)>,
+    descend: bool,
+    done: bool,
+}
+
+impl StepSolver {
+    /// Prepares to solve `board`. A board that already breaks a rule takes no steps.
+    pub fn new(board: &SudokuBoard) -> Self {
+        StepSolver {
+            board: *board,
+            stack: Vec::new(),
+            descend: true,
+            done: !board.is_consistent(),
+        }
+    }
+
+    /// Borrows the board as it stands after the steps taken so far.
+    pub fn board(&self) -> &SudokuBoard {
+        &self.board
+    }
+
+    /// Takes the next action and describes it, or returns None once the board is solved or
+    /// proven unsolvable.
+    pub fn step(&mut self) -> Option<SolverStep> {
+        if self.done {
+            return None;
+        }
+        loop {
+            if self.descend {
+                let Some(empty_cell) = BacktrackingSolver::find_most_constrained_cell(&self.board) else {
+                    self.done = true; // No empty cells, board is solved
+                    return None;
+                };
+                self.stack.push((empty_cell, 0));
+                self.descend = false;
+            }
+
+            let Some(&(cell, current)) = self.stack.last() else {
+                self.done = true; // Every option failed, board is unsolvable
+                return None;
+            };
+            if current != 0 && self.board.get(cell) == Some(current) {
+                self.board.unplace_fast(cell, current);
+                return Some(SolverStep::Backtrack { cell });
+            }
+            match (current + 1..=9).find(|&num| self.board.is_placement_valid(cell, num)) {
+                Some(num) => {
+                    self.board.place_fast(cell, num);
+                    self.stack.last_mut().unwrap().1 = num;
+                    self.descend = true;
+                    return Some(SolverStep::Place { cell, num });
+                }
+                // Out of numbers here, so the previous branch has to be taken back.
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "extra_solvers")]
+/// The deduction behind a logical placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Technique {
+    /// The cell has only one candidate left.
+    NakedSingle,
+    /// The number fits in only one cell of a row, column or box.
+    HiddenSingle,
+}
+
+#[cfg(feature = "extra_solvers")]
+/// Whether a trace step filled a cell or took a number back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum StepKind {
+    Place,
+    Backtrack,
+}
+
+#[cfg(feature = "extra_solvers")]
+/// One step of a full solve, as recorded by `solve_trace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceStep {
+    /// The cell as `[row, col]`.
+    pub cell: [u8; 2],
+    /// The number placed, or for a backtrack the number taken back.
+    pub value: u8,
+    pub kind: StepKind,
+    /// The deduction behind a placement, or None for a guess or a backtrack.
+    pub technique: Option<Technique>,
+}
+
+#[cfg(feature = "extra_solvers")]
+/// Records a complete solve for replay: every hint `next_hint` can find, then, once hints
+/// run out, every placement and backtrack `StepSolver` makes. Returns None if the puzzle
+/// has no solution or already breaks a rule.
+pub fn solve_trace(board: &SudokuBoard) -> Option<Vec<TraceStep>> {
+    if !board.is_consistent() {
+        return None;
+    }
+
+    let mut board = *board;
+    let mut trace = Vec::new();
+    while let Some(hint) = next_hint(&board) {
+        board.place_fast(hint.cell, hint.value);
+        let cell = [hint.cell.0, hint.cell.1];
+        trace.push(TraceStep { cell, value: hint.value, kind: StepKind::Place, technique: Some(hint.technique) });
+    }
+
+    let mut solver = StepSolver::new(&board);
+    loop {
+        let before = *solver.board();
+        let step = match solver.step() {
+            Some(SolverStep::Place { cell, num }) => (cell, num, StepKind::Place),
+            Some(SolverStep::Backtrack { cell }) => (cell, before.get(cell).unwrap(), StepKind::Backtrack),
+            None => break,
+        };
+        let ((r, c), value, kind) = step;
+        trace.push(TraceStep { cell: [r, c], value, kind, technique: None });
+    }
+    solver.board().is_solved().then_some(trace)
+}
+
+#[cfg(all(feature = "extra_solvers", feature = "serde"))]
+/// `solve_trace` as a JSON array of `{"cell": [r, c], "value", "kind", "technique"}`
+/// objects, for a front-end to animate. `kind` is `"place"` or `"backtrack"`, and
+/// `technique` is `"naked_single"`, `"hidden_single"` or null.
+pub fn solve_trace_json(board: &SudokuBoard) -> Option<String> {
+    solve_trace(board).map(|trace| serde_json::to_string(&trace).expect("trace steps always serialize"))
+}
+
+#[cfg(feature = "extra_solvers")]
+/// Solves by deduction alone, the way a person would, without guessing.
+pub struct LogicSolver;
+
+#[cfg(feature = "extra_solvers")]
+// The techniques `LogicSolver` knows, easiest first. Rating a puzzle means finding the
+// easiest pass that still solves it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Pass {
+    NakedSingles,
+    HiddenSingles,
+    Pairs,
+    Intersections,
+    Triples,
+    XWing,
+    Swordfish,
+    Coloring,
+}
+
+#[cfg(feature = "extra_solvers")]
+impl LogicSolver {
+    /// Repeatedly fills naked singles (cells with one candidate) and hidden singles (numbers
+    /// with one possible cell in a row, column or box), and narrows candidates with naked and
+    /// hidden pairs, box/line intersections, naked and hidden triples, X-Wings, Swordfish and
+    /// simple coloring, until nothing makes progress. Every placement is left on `board`.
+    /// Returns true if the board ends up solved.
+    pub fn solve(board: &mut SudokuBoard) -> bool {
+        Self::solve_with(board, Pass::Coloring)
+    }
+
+    // Runs every pass up to and including `hardest`, going back to the easiest after each
+    // one that makes progress.
+    fn solve_with(board: &mut SudokuBoard, hardest: Pass) -> bool {
+        let mut grid = CandidateGrid::new(board);
+        while Self::step(&mut grid, hardest) {}
+        *board = *grid.board();
+        board.is_solved()
+    }
+
+    fn step(grid: &mut CandidateGrid, hardest: Pass) -> bool {
+        let passes = [
+            Pass::NakedSingles,
+            Pass::HiddenSingles,
+            Pass::Pairs,
+            Pass::Intersections,
+            Pass::Triples,
+            Pass::XWing,
+            Pass::Swordfish,
+            Pass::Coloring,
+        ];
+        passes
+            .into_iter()
+            .take_while(|&pass| pass <= hardest)
+            .any(|pass| match pass {
+                Pass::NakedSingles => Self::apply_naked_single(grid),
+                Pass::HiddenSingles => Self::apply_hidden_single(grid),
+                Pass::Pairs => Self::apply_naked_set(grid, 2) || Self::apply_hidden_set(grid, 2),
+                Pass::Intersections => {
+                    Self::apply_intersection(grid, true) || Self::apply_intersection(grid, false)
+                }
+                Pass::Triples => Self::apply_naked_set(grid, 3) || Self::apply_hidden_set(grid, 3),
+                Pass::XWing => Self::apply_fish(grid, 2),
+                Pass::Swordfish => Self::apply_fish(grid, 3),
+                Pass::Coloring => Self::apply_simple_coloring(grid),
+            })
+    }
+
+    // Fills the first cell that has exactly one candidate. Returns false if there is none.
+    fn apply_naked_single(grid: &mut CandidateGrid) -> bool {
+        Self::apply(grid, Self::find_naked_single(grid))
+    }
+
+    // Fills the first hidden single found in any unit. Returns false if there is none.
+    fn apply_hidden_single(grid: &mut CandidateGrid) -> bool {
+        Self::apply(grid, Self::find_hidden_single(grid))
+    }
+
+    fn apply(grid: &mut CandidateGrid, found: Option<Placement>) -> bool {
+        match found {
+            Some((cell, num)) => grid.assign(cell, num).is_ok(),
+            None => false,
+        }
+    }
+
+    // The first cell, in row-major order, that has exactly one candidate.
+    fn find_naked_single(grid: &CandidateGrid) -> Option<Placement> {
+        grid.board().iter_empty().find_map(|cell| {
+            let mask = grid.candidates_mask(cell);
+            (mask.count_ones() == 1).then(|| (cell, mask.trailing_zeros() as u8))
+        })
+    }
+
+    // The first hidden single in any unit, rows first, then columns, then boxes.
+    fn find_hidden_single(grid: &CandidateGrid) -> Option<Placement> {
+        grid.board().units().find_map(|(_, _, cells)| {
+            (1..=9).find_map(|num| {
+                let mut spots = cells.iter().filter(|&&cell| grid.candidates_mask(cell) & (1 << num) != 0);
+                match (spots.next(), spots.next()) {
+                    (Some(&cell), None) => Some((cell, num)),
+                    _ => None,
+                }
+            })
+        })
+    }
+
+    // Naked sets: if `size` cells of a unit have only `size` candidates between them, those
+    // cells must hold exactly those numbers, so no other cell in the unit can (naked pairs and
+    // triples). Returns true if any candidate was removed.
+    fn apply_naked_set(grid: &mut CandidateGrid, size: u32) -> bool {
+        let units: Vec<_> = grid.board().units().map(|(_, _, cells)| cells).collect();
+        for cells in units {
+            let masks: [u16; 9] = core::array::from_fn(|i| grid.candidates_mask(cells[i]));
+            // Bit i of `chosen` picks cells[i].
+            for chosen in subsets(9, size) {
+                let members = (0..9).filter(|&i| chosen & (1 << i) != 0);
+                if members.clone().any(|i| masks[i] == 0) {
+                    continue;
+                }
+                let union = members.fold(0, |acc, i| acc | masks[i]);
+                if union.count_ones() != size {
+                    continue;
+                }
+                let mut changed = false;
+                for i in (0..9).filter(|&i| chosen & (1 << i) == 0) {
+                    changed |= grid.restrict(cells[i], !union);
+                }
+                if changed {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // Hidden sets: if `size` numbers can only go in the same `size` cells of a unit, those
+    // cells must hold exactly those numbers, so neither can hold anything else (hidden pairs
+    // and triples). Returns true if any candidate was removed.
+    fn apply_hidden_set(grid: &mut CandidateGrid, size: u32) -> bool {
+        let units: Vec<_> = grid.board().units().map(|(_, _, cells)| cells).collect();
+        for cells in units {
+            // spots[n] has bit i set if n is still a candidate for cells[i].
+            let spots: [u16; 10] = core::array::from_fn(|num| {
+                (0..9)
+                    .filter(|&i| grid.candidates_mask(cells[i]) & (1 << num) != 0)
+                    .fold(0, |acc, i| acc | 1 << i)
+            });
+            // Bit n of `chosen` picks the number n, as in a candidate mask.
+            for chosen in subsets(9, size).map(|numbers| numbers << 1) {
+                let numbers = (1..=9).filter(|&num| chosen & (1 << num) != 0);
+                if numbers.clone().any(|num| spots[num] == 0) {
+                    continue;
+                }
+                let union = numbers.fold(0, |acc, num| acc | spots[num]);
+                if union.count_ones() != size {
+                    continue;
+                }
+                let mut changed = false;
+                for i in (0..9).filter(|&i| union & (1 << i) != 0) {
+                    changed |= grid.restrict(cells[i], chosen);
+                }
+                if changed {
+                    return true;
+                }
+            }
         }
-        None
+        false
     }
 
-    fn recursive_solve(board: &mut SudokuBoard) -> bool {
-        if let Some(empty_cell) = Self::find_first_empty_cell(board) {
+    // If a number's spots in a box all lie in one row or column, the number must go in that
+    // part of the line, so the rest of the line can't hold it (pointing). Likewise, spots in a
+    // line confined to one box rule it out of the rest of the box (box-line reduction).
+    // `from_boxes` picks which of the two to look for. Returns true if any candidate was
+    // removed.
+    fn apply_intersection(grid: &mut CandidateGrid, from_boxes: bool) -> bool {
+        let units: Vec<_> = grid.board().units().collect();
+        for &(kind, _, cells) in units.iter().filter(|unit| (unit.0 == UnitKind::Box) == from_boxes) {
             for num in 1..=9 {
-                if board.is_placement_valid(empty_cell, num) {
-                    board.internal_place(empty_cell, num);
-                    if Self::recursive_solve(board) {
+                let bit = 1 << num;
+                let spots: Vec<_> =
+                    cells.iter().copied().filter(|&cell| grid.candidates_mask(cell) & bit != 0).collect();
+                if spots.is_empty() {
+                    continue;
+                }
+                // Only a box and a line can share more than one cell.
+                let crossing = units
+                    .iter()
+                    .filter(|&&(other, _, _)| (kind == UnitKind::Box) != (other == UnitKind::Box));
+                for (_, _, other_cells) in crossing {
+                    if !spots.iter().all(|spot| other_cells.contains(spot)) {
+                        continue;
+                    }
+                    let mut changed = false;
+                    for &cell in other_cells.iter().filter(|cell| !cells.contains(cell)) {
+                        changed |= grid.restrict(cell, !bit);
+                    }
+                    if changed {
                         return true;
                     }
-                    board.internal_place(empty_cell, 0); 
                 }
             }
-            false // No number worked, need to backtrack
-        } else {
-            true // No empty cells, board is solved
         }
+        false
     }
 
-    fn find_first_empty_cell(board: &SudokuBoard) -> Option<(u8, u8)> {
-        for r in 0..9 {
-            for c in 0..9 {
-                if board.get((r, c)) == Some(0) {
-                    return Some((r, c));
-                }
-            }
-        }
-        // No empty cells were found.
-        None
-    }
+    // Fish: if a number's spots in `size` different rows all fall within the same `size`
+    // columns, those rows take it in those columns, so no other row can have it there (an
+    // X-Wing for two, a Swordfish for three). The same goes with rows and columns swapped.
+    // Returns true if any candidate was removed.
+    fn apply_fish(grid: &mut CandidateGrid, size: u32) -> bool {
+        for by_rows in [true, false] {
+            // Cell `pos` along `line`, where lines are rows or columns depending on the pass.
+            let at = |line: u8, pos: u8| if by_rows { (line, pos) } else { (pos, line) };
+            for num in 1..=9 {
+                let bit = 1 << num;
+                // spots[line] has bit p set if `num` is still a candidate at position p.
+                let spots: [u16; 9] = core::array::from_fn(|line| {
+                    (0..9)
+                        .filter(|&pos| grid.candidates_mask(at(line as u8, pos)) & bit != 0)
+                        .fold(0, |acc, pos| acc | 1 << pos)
+                });
+                // Bit l of `chosen` picks line l. A line that already holds `num` has no spots.
+                for chosen in subsets(9, size) {
+                    let lines = (0..9).filter(|&line| chosen & (1 << line) != 0);
+                    if lines.clone().any(|line| spots[line] == 0) {
+                        continue;
+                    }
+                    let union = lines.fold(0, |acc, line| acc | spots[line]);
+                    if union.count_ones() != size {
+                        continue;
+                    }
+                    let mut changed = false;
+                    for pos in (0..9).filter(|&pos| union & (1 << pos) != 0) {
+                        for line in (0..9).filter(|&line| chosen & (1 << line) == 0) {
+                            changed |= grid.restrict(at(line, pos), !bit);
+                        }
+                    }
+                    if changed {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    // Removes whatever `CandidateGrid::find_simple_coloring` rules out. Returns true if any
+    // candidate was removed.
+    fn apply_simple_coloring(grid: &mut CandidateGrid) -> bool {
+        let Some(found) = grid.find_simple_coloring() else {
+            return false;
+        };
+        found.cells.iter().fold(false, |changed, &cell| grid.eliminate(cell, found.value) | changed)
+    }
+}
+
+#[cfg(feature = "extra_solvers")]
+// Every `size`-element subset of `0..n`, as bitmasks in ascending order.
+fn subsets(n: u32, size: u32) -> impl Iterator<Item = u16> {
+    (0..1u16 << n).filter(move |set| set.count_ones() == size)
+}
+
+#[cfg(feature = "extra_solvers")]
+/// A single logical move: place `value` at `cell`, because of `technique`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    pub cell: (u8, u8),
+    pub value: u8,
+    pub technique: Technique,
+}
+
+#[cfg(feature = "extra_solvers")]
+/// Suggests the easiest next move, preferring naked singles over hidden singles. Returns
+/// None if no single-step deduction exists, or if the board already breaks a rule.
+pub fn next_hint(board: &SudokuBoard) -> Option<Hint> {
+    if !board.is_consistent() {
+        return None;
+    }
+    let grid = CandidateGrid::new(board);
+    let hint = |(cell, value), technique| Hint { cell, value, technique };
+    LogicSolver::find_naked_single(&grid)
+        .map(|found| hint(found, Technique::NakedSingle))
+        .or_else(|| LogicSolver::find_hidden_single(&grid).map(|found| hint(found, Technique::HiddenSingle)))
+}
+
+#[cfg(feature = "extra_solvers")]
+/// How hard a puzzle is, judged by the hardest technique needed to solve it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    /// Naked singles (cells with only one candidate) are enough.
+    Easy,
+    /// Hidden singles are needed as well.
+    Medium,
+    /// Singles stall, but pairs, box/line intersections or triples get them going again.
+    Hard,
+    /// Only an X-Wing or a Swordfish gets past where the easier techniques stall.
+    Expert,
+    /// Only simple coloring, following chains of a single number, gets past where the easier
+    /// techniques stall.
+    Master,
+    /// Every technique the logic solver knows stalls, so solving takes guessing.
+    Extreme,
+    /// The puzzle has no solution or more than one, so it can't be rated.
+    NotUnique,
+}
+
+#[cfg(feature = "extra_solvers")]
+// Each rating short of Extreme, with the hardest pass it allows.
+const TIERS: [(Pass, Difficulty); 5] = [
+    (Pass::NakedSingles, Difficulty::Easy),
+    (Pass::HiddenSingles, Difficulty::Medium),
+    (Pass::Triples, Difficulty::Hard),
+    (Pass::Swordfish, Difficulty::Expert),
+    (Pass::Coloring, Difficulty::Master),
+];
+
+#[cfg(feature = "extra_solvers")]
+/// Rates a puzzle by running the logic solver and noting the hardest technique it needs.
+pub fn rate_difficulty(board: &SudokuBoard) -> Difficulty {
+    if BacktrackingSolver::count_solutions(board, 2) != 1 {
+        return Difficulty::NotUnique;
+    }
+
+    TIERS
+        .into_iter()
+        .find(|&(pass, _)| LogicSolver::solve_with(&mut board.clone(), pass))
+        .map_or(Difficulty::Extreme, |(_, difficulty)| difficulty)
+}
+
+#[cfg(feature = "extra_solvers")]
+// Whether the logic solver gets through the board using only the techniques `difficulty`
+// allows. Extreme allows guessing, so it always does; NotUnique never does.
+pub(crate) fn solves_within(board: &SudokuBoard, difficulty: Difficulty) -> bool {
+    match difficulty {
+        Difficulty::Extreme => true,
+        Difficulty::NotUnique => false,
+        _ => TIERS
+            .into_iter()
+            .find(|&(_, tier)| tier == difficulty)
+            .is_some_and(|(pass, _)| LogicSolver::solve_with(&mut board.clone(), pass)),
+    }
+}
+
+#[cfg(feature = "extra_solvers")]
+/// Solves by exact cover using Knuth's Dancing Links (Algorithm X). Much faster than
+/// `BacktrackingSolver` on the hardest puzzles, and returns the same solution for any
+/// puzzle that has exactly one.
+///
+/// The solver owns its node arena, so solving a batch with one solver rebuilds the matrix
+/// in place instead of allocating and freeing it for every puzzle.
+pub struct DancingLinksSolver {
+    links: Links,
+    chosen: Vec<usize>,
+}
+
+#[cfg(feature = "extra_solvers")]
+impl DancingLinksSolver {
+    /// Solves the board, returning None if it is unsolvable or already breaks a rule.
+    pub fn run(init_board: &SudokuBoard) -> Option<SudokuBoard> {
+        Self::with_arena().solve(init_board)
+    }
+
+    /// A solver with its node arena allocated up front, large enough for any puzzle, and
+    /// kept across calls to `solve`.
+    pub fn with_arena() -> Self {
+        DancingLinksSolver { links: Links::with_capacity(), chosen: Vec::with_capacity(81) }
+    }
+
+    /// Solves the board like `run`, but rebuilds the matrix in this solver's arena instead
+    /// of allocating a new one.
+    pub fn solve(&mut self, init_board: &SudokuBoard) -> Option<SudokuBoard> {
+        if !init_board.is_consistent() {
+            return None;
+        }
+
+        self.links.reset(init_board);
+        self.chosen.clear();
+        if !self.links.search(&mut self.chosen) {
+            return None;
+        }
+
+        let mut board = *init_board;
+        for &row in &self.chosen {
+            let (cell, num) = self.links.placements[row];
+            if board.get(cell) == Some(0) {
+                board.place_fast(cell, num);
+            }
+        }
+        Some(board)
+    }
+}
+
+#[cfg(feature = "extra_solvers")]
+// Number of exact cover constraints: each cell filled once, and each number once per row,
+// column and box.
+const CONSTRAINTS: usize = 4 * 81;
+
+#[cfg(feature = "extra_solvers")]
+// Most nodes the matrix can hold: the root and headers, plus four for each of the 729
+// possible placements.
+const MAX_NODES: usize = CONSTRAINTS + 1 + 4 * 729;
+
+#[cfg(feature = "extra_solvers")]
+// The exact cover matrix as toroidal doubly linked lists. Node 0 is the root, nodes
+// 1..=CONSTRAINTS are the column headers, and each candidate placement adds a row of four
+// nodes after that, one per constraint it satisfies.
+struct Links {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    // Index into placements for every non-header node.
+    row: Vec<usize>,
+    // Nodes currently in each column, indexed by header.
+    size: Vec<usize>,
+    placements: Vec<Placement>,
+}
+
+#[cfg(feature = "extra_solvers")]
+impl Links {
+    // An empty matrix with room for the largest one, so `reset` never has to grow it.
+    fn with_capacity() -> Self {
+        Links {
+            left: Vec::with_capacity(MAX_NODES),
+            right: Vec::with_capacity(MAX_NODES),
+            up: Vec::with_capacity(MAX_NODES),
+            down: Vec::with_capacity(MAX_NODES),
+            column: Vec::with_capacity(MAX_NODES),
+            row: Vec::with_capacity(MAX_NODES),
+            size: vec![0; CONSTRAINTS + 1],
+            placements: Vec::with_capacity(729),
+        }
+    }
+
+    // Rebuilds the matrix for the board, in place: one row for each filled cell's value, and
+    // one for every candidate of each empty cell.
+    fn reset(&mut self, board: &SudokuBoard) {
+        let headers = CONSTRAINTS + 1;
+        let lists = [&mut self.left, &mut self.right, &mut self.up, &mut self.down, &mut self.column, &mut self.row];
+        for list in lists {
+            list.clear();
+        }
+        self.left.extend((0..headers).map(|i| (i + headers - 1) % headers));
+        self.right.extend((0..headers).map(|i| (i + 1) % headers));
+        self.up.extend(0..headers);
+        self.down.extend(0..headers);
+        self.column.extend(0..headers);
+        self.row.resize(headers, usize::MAX);
+        self.size.fill(0);
+        self.placements.clear();
+
+        for r in 0..9 {
+            for c in 0..9 {
+                match board.get((r, c)).unwrap() {
+                    0 => {
+                        for num in (1..=9).filter(|&num| board.is_placement_valid((r, c), num)) {
+                            self.add_row(((r, c), num));
+                        }
+                    }
+                    num => self.add_row(((r, c), num)),
+                }
+            }
+        }
+    }
+
+    fn add_row(&mut self, placement: Placement) {
+        let ((r, c), num) = placement;
+        let (r, c, d) = (r as usize, c as usize, num as usize - 1);
+        let b = (r / 3) * 3 + c / 3;
+        let columns = [1 + r * 9 + c, 82 + r * 9 + d, 163 + c * 9 + d, 244 + b * 9 + d];
+
+        let row = self.placements.len();
+        self.placements.push(placement);
+        let first = self.left.len();
+        for (i, &col) in columns.iter().enumerate() {
+            let node = first + i;
+            let last = self.up[col];
+            self.left.push(if i == 0 { first + 3 } else { node - 1 });
+            self.right.push(if i == 3 { first } else { node + 1 });
+            self.up.push(last);
+            self.down.push(col);
+            self.down[last] = node;
+            self.up[col] = node;
+            self.column.push(col);
+            self.row.push(row);
+            self.size[col] += 1;
+        }
+    }
+
+    // Removes a column from the header list and every row that has a node in it.
+    fn cover(&mut self, col: usize) {
+        self.right[self.left[col]] = self.right[col];
+        self.left[self.right[col]] = self.left[col];
+        let mut i = self.down[col];
+        while i != col {
+            let mut j = self.right[i];
+            while j != i {
+                self.down[self.up[j]] = self.down[j];
+                self.up[self.down[j]] = self.up[j];
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    // Exactly reverses cover(col).
+    fn uncover(&mut self, col: usize) {
+        let mut i = self.up[col];
+        while i != col {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                self.down[self.up[j]] = j;
+                self.up[self.down[j]] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        self.right[self.left[col]] = col;
+        self.left[self.right[col]] = col;
+    }
+
+    // Algorithm X, always branching on the column with the fewest rows. On success the
+    // chosen rows are left in `chosen` and the matrix is left partly covered.
+    fn search(&mut self, chosen: &mut Vec<usize>) -> bool {
+        if self.right[0] == 0 {
+            return true;
+        }
+
+        let mut col = self.right[0];
+        let mut j = self.right[col];
+        while j != 0 {
+            if self.size[j] < self.size[col] {
+                col = j;
+            }
+            j = self.right[j];
+        }
+
+        self.cover(col);
+        let mut r = self.down[col];
+        while r != col {
+            chosen.push(self.row[r]);
+            let mut j = self.right[r];
+            while j != r {
+                self.cover(self.column[j]);
+                j = self.right[j];
+            }
+            if self.search(chosen) {
+                return true;
+            }
+            let mut j = self.left[r];
+            while j != r {
+                self.uncover(self.column[j]);
+                j = self.left[j];
+            }
+            chosen.pop();
+            r = self.down[r];
+        }
+        self.uncover(col);
+        false
+    }
+}
+
+/// Lazily yields every solution of `board`, in the order the backtracking search finds
+/// them. Nothing is computed until the iterator is advanced, so `solutions(&b).take(10)`
+/// stays cheap even on a nearly empty board. Boards that break a rule yield nothing.
+pub fn solutions(board: &SudokuBoard) -> impl Iterator<Item = SudokuBoard> {
+    Solutions {
+        board: *board,
+        stack: Vec::new(),
+        started: false,
+        done: !board.is_consistent(),
+    }
+}
+
+// Backtracking search with an explicit stack, so it can pause after each solution.
+struct Solutions {
+    board: SudokuBoard,
+    // Branch cells and the number currently placed in each (0 before the first try).
+    stack: Vec<((u8, u8), u8)>,
+    started: bool,
+    done: bool,
+}
+
+impl Solutions {
+    // Moves the deepest branch to its next valid number, dropping branches that have run
+    // out. Returns false once the whole search space is exhausted.
+    fn advance(&mut self) -> bool {
+        while let Some(&(cell, current)) = self.stack.last() {
+            if current != 0 {
+                self.board.unplace_fast(cell, current);
+            }
+            if let Some(num) = (current + 1..=9).find(|&num| self.board.is_placement_valid(cell, num)) {
+                self.board.place_fast(cell, num);
+                self.stack.last_mut().unwrap().1 = num;
+                return true;
+            }
+            self.stack.pop();
+        }
+        false
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = SudokuBoard;
+
+    fn next(&mut self) -> Option<SudokuBoard> {
+        if self.done {
+            return None;
+        }
+        // After a solution has been yielded, backtrack out of it before searching again.
+        let mut descend = !self.started;
+        self.started = true;
+        loop {
+            if descend {
+                match self.board.cell_by_candidate_count(false) {
+                    Some(cell) => self.stack.push((cell, 0)),
+                    None => return Some(self.board),
+                }
+            }
+            if !self.advance() {
+                self.done = true;
+                return None;
+            }
+            descend = true;
+        }
+    }
+}
+
+#[cfg(feature = "extra_solvers")]
+/// Finds every minimal unavoidable set of a solved grid with at most `max_size` cells, each
+/// in row-major order, smallest sets first. An unavoidable set is a group of cells that
+/// could be refilled another way without breaking a rule, so any puzzle with this solution
+/// needs a clue in each one to be unique. Returns nothing if `solution` isn't a solved grid.
+///
+/// Every number in such a set appears at least twice, so the search blanks each choice of
+/// up to `max_size / 2` numbers and diffs every other completion against the solution. The
+/// cost grows quickly with `max_size`; 4 to 8 is the useful range.
+pub fn unavoidable_sets(solution: &SudokuBoard, max_size: usize) -> Vec<Vec<(u8, u8)>> {
+    let grid = solution.get_board();
+    if grid.iter().flatten().any(|&value| value == 0) || !SudokuBoard::is_valid_config(grid) {
+        return Vec::new();
+    }
+
+    let mut sets: Vec<Vec<(u8, u8)>> = Vec::new();
+    // Bit n - 1 of `digits` is set if n gets blanked.
+    let choices = (0u16..1 << 9).filter(|digits| (2..=max_size / 2).contains(&(digits.count_ones() as usize)));
+    for digits in choices {
+        let mut config = *grid;
+        for value in config.iter_mut().flatten() {
+            if digits & (1 << (*value - 1)) != 0 {
+                *value = 0;
+            }
+        }
+        let board = SudokuBoard::from(config).expect("a solved grid with cells blanked is still valid");
+        for other in solutions(&board) {
+            let diff: Vec<_> = solution
+                .iter_cells()
+                .filter(|&(cell, value)| other.get(cell) != Some(value))
+                .map(|(cell, _)| cell)
+                .collect();
+            if !diff.is_empty() && diff.len() <= max_size && !sets.contains(&diff) {
+                sets.push(diff);
+            }
+        }
+    }
+
+    // Anything found that isn't minimal contains a smaller set that was found too.
+    let contains = |outer: &[(u8, u8)], inner: &[(u8, u8)]| inner.iter().all(|cell| outer.contains(cell));
+    let mut minimal: Vec<_> = sets
+        .iter()
+        .filter(|&set| !sets.iter().any(|other| other.len() < set.len() && contains(set, other)))
+        .cloned()
+        .collect();
+    minimal.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    minimal
+}
+
+#[cfg(feature = "extra_solvers")]
+/// Differential check for tests: runs every available solver on `board` and returns true
+/// if they all agree on whether it is solvable, and every solution returned is complete,
+/// valid, and keeps the board's filled cells. On a board with several solutions the
+/// solvers may legitimately return different ones.
+pub fn solvers_agree(board: &SudokuBoard) -> bool {
+    let mut steps = StepSolver::new(board);
+    while steps.step().is_some() {}
+    let sized = Board9::from_classic(board).ok().and_then(|sized| BacktrackingSolver::run_sized(&sized));
+
+    let mut results = vec![
+        BacktrackingSolver::run(board),
+        BacktrackingSolver::run_random(board, 0),
+        DancingLinksSolver::run(board),
+        steps.board().is_solved().then_some(*steps.board()),
+        sized.map(|sized| SudokuBoard::from_flat(sized.as_flat()).expect("a solved grid is a valid config")),
+    ];
+    #[cfg(feature = "std")]
+    results.push(BacktrackingSolver::run_parallel(board));
+
+    let solvable = results[0].is_some();
+    results.iter().all(|result| match result {
+        Some(solved) => solvable && is_completion_of(solved, board),
+        None => !solvable,
+    })
+}
+
+#[cfg(feature = "extra_solvers")]
+// Whether `solved` is a complete, valid grid that keeps every filled cell of `board`.
+fn is_completion_of(solved: &SudokuBoard, board: &SudokuBoard) -> bool {
+    solved.is_complete()
+        && solved.is_consistent()
+        && board.as_flat().iter().zip(solved.as_flat()).all(|(&before, &after)| before == 0 || before == after)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
 
     // A solvable Sudoku configuration for use in tests.
     fn solvable_config() -> [[u8; 9]; 9] {
-        [
-            [0, 0, 6, 0, 4, 0, 0, 9, 7],
-            [0, 4, 0, 7, 3, 0, 0, 1, 0],
-            [0, 1, 7, 0, 9, 2, 0, 3, 0],
-            [6, 0, 0, 0, 7, 0, 0, 8, 0],
-            [1, 0, 5, 0, 6, 0, 9, 0, 3],
-            [0, 2, 0, 0, 1, 0, 0, 0, 6],
-            [0, 5, 0, 9, 8, 0, 1, 6, 0],
-            [0, 9, 0, 0, 5, 6, 0, 7, 0],
-            [8, 6, 0, 0, 2, 0, 3, 0, 0],
-        ]
+        *fixtures::board(fixtures::SINGLES).get_board()
+    }
+
+    // Arto Inkala's "world's hardest Sudoku".
+    fn hardest_config() -> [[u8; 9]; 9] {
+        *fixtures::board(fixtures::HARDEST).get_board()
     }
 
     // An unsolvable but valid configuration.
@@ -84,9 +1232,763 @@ mod tests {
         assert!(BacktrackingSolver::run(&board).is_some());
     }
 
+    #[test]
+    fn test_solution_wraps_solved_board() {
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        let solution = BacktrackingSolver::solution(&board).unwrap();
+        let solved = BacktrackingSolver::run(&board).unwrap();
+        assert_eq!(solution.as_board().get_board(), solved.get_board());
+        assert_eq!(BacktrackingSolver::solution(&board), Some(solution));
+        assert_eq!(solution.into_board().get_board(), solved.get_board());
+
+        let board = SudokuBoard::from(unsolvable_config()).unwrap();
+        assert_eq!(BacktrackingSolver::solution(&board), None);
+    }
+
+    #[test]
+    fn test_run_outcome() {
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        assert!(matches!(BacktrackingSolver::run_outcome(&board), SolveOutcome::Solved(_)));
+
+        let board = SudokuBoard::from(unsolvable_config()).unwrap();
+        assert_eq!(BacktrackingSolver::run_outcome(&board), SolveOutcome::Unsolvable);
+
+        // Force a duplicate 6 into the first row, bypassing the board's checks.
+        let mut board = SudokuBoard::from(solvable_config()).unwrap();
+        board.internal_place((0, 0), 6);
+        assert_eq!(BacktrackingSolver::run_outcome(&board), SolveOutcome::Aborted(SolveError::InconsistentBoard));
+        assert!(BacktrackingSolver::run(&board).is_none());
+    }
+
+    #[test]
+    fn test_finish_keeps_player_entries() {
+        // (0, 0) is 3 in the solution.
+        let mut board = SudokuBoard::from(solvable_config()).unwrap();
+        board.try_place((0, 0), 3).unwrap();
+        let finished = BacktrackingSolver::finish(&board).unwrap();
+        assert_eq!(finished.get((0, 0)), Some(3));
+        assert_eq!(finished.get_board(), BacktrackingSolver::run(&board).unwrap().get_board());
+    }
+
+    #[test]
+    fn test_finish_wrong_entry_vs_unsolvable_puzzle() {
+        // (0, 3) is 5 in the unique solution, so a legal-looking 1 is a player mistake.
+        let mut board = SudokuBoard::from(solvable_config()).unwrap();
+        board.try_place((0, 3), 1).unwrap();
+        assert_eq!(BacktrackingSolver::finish(&board).err(), Some(SolveError::ContradictsEntries));
+
+        // Without any entries the unsolvable fixture fails because of its givens.
+        let board = SudokuBoard::from(unsolvable_config()).unwrap();
+        assert_eq!(BacktrackingSolver::finish(&board).err(), Some(SolveError::UnsolvableGivens));
+    }
+
+    #[test]
+    fn test_solve_determinism() {
+        // Keep only the first row of givens so the board has many solutions.
+        let mut config = [[0; 9]; 9];
+        config[0] = solvable_config()[0];
+        let board = SudokuBoard::from(config).unwrap();
+
+        let first = BacktrackingSolver::run(&board).unwrap();
+        for _ in 0..100 {
+            let again = BacktrackingSolver::run(&board).unwrap();
+            assert_eq!(again.get_board(), first.get_board());
+        }
+    }
+
+    // A plain backtracker that always fills the first empty cell in row-major order, counting
+    // its placements in `nodes`. The baseline MRV is measured against.
+    fn row_major_search(board: &mut SudokuBoard, nodes: &mut u32) -> bool {
+        let Some(cell) = (0..81).map(|i| (i / 9, i % 9)).find(|&cell| board.get(cell) == Some(0)) else {
+            return true;
+        };
+        for num in 1..=9 {
+            if board.is_placement_valid(cell, num) {
+                board.place_fast(cell, num);
+                *nodes += 1;
+                if row_major_search(board, nodes) {
+                    return true;
+                }
+                board.unplace_fast(cell, num);
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn test_mrv_reduces_nodes_on_hardest_puzzle() {
+        let board = SudokuBoard::from(hardest_config()).unwrap();
+        let mut baseline = 0;
+        let mut row_major = board;
+        assert!(row_major_search(&mut row_major, &mut baseline));
+
+        let (solved, stats) = BacktrackingSolver::run_with_stats(&board);
+        assert_eq!(solved, Some(row_major));
+        assert!(stats.nodes < baseline / 4, "MRV took {} placements, row-major order {}", stats.nodes, baseline);
+    }
+
+    #[test]
+    fn test_run_with_stats() {
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        let (solved, stats) = BacktrackingSolver::run_with_stats(&board);
+        assert_eq!(solved, BacktrackingSolver::run(&board));
+        // A successful search ends with every empty cell filled once, on the deepest path.
+        assert_eq!(stats.max_depth, board.empty_count() as u32);
+        assert_eq!(stats.nodes - stats.backtracks, stats.max_depth);
+
+        // A full board needs no search at all.
+        let (_, stats) = BacktrackingSolver::run_with_stats(&solved.unwrap());
+        assert_eq!(stats, SolveStats::default());
+
+        let (solved, stats) = BacktrackingSolver::run_with_stats(&SudokuBoard::from(unsolvable_config()).unwrap());
+        assert_eq!(solved, None);
+        assert_eq!(stats.nodes, stats.backtracks);
+    }
+
+    #[test]
+    fn test_difficulty_proxy() {
+        let easy = SudokuBoard::from(solvable_config()).unwrap();
+        let hard = SudokuBoard::from(hardest_config()).unwrap();
+        assert!(BacktrackingSolver::difficulty_proxy(&easy) < BacktrackingSolver::difficulty_proxy(&hard));
+
+        // A solved board needs no effort at all.
+        let solved = BacktrackingSolver::run(&easy).unwrap();
+        assert_eq!(BacktrackingSolver::difficulty_proxy(&solved), 0);
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_logic_solver_solves_singles_puzzle() {
+        // The sample puzzle needs both naked and hidden singles.
+        let mut board = SudokuBoard::from(solvable_config()).unwrap();
+        assert!(LogicSolver::solve(&mut board));
+        let expected = BacktrackingSolver::run(&SudokuBoard::from(solvable_config()).unwrap()).unwrap();
+        assert_eq!(board.get_board(), expected.get_board());
+
+        let mut naked_only = SudokuBoard::from(solvable_config()).unwrap();
+        assert!(!LogicSolver::solve_with(&mut naked_only, Pass::NakedSingles));
+        assert!(!naked_only.is_complete());
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_logic_solver_stalls_without_guessing() {
+        let mut board = SudokuBoard::from(hardest_config()).unwrap();
+        assert!(!LogicSolver::solve(&mut board));
+
+        // Whatever it filled in is still consistent with the real solution.
+        assert!(!board.is_complete());
+        let solution = BacktrackingSolver::run(&SudokuBoard::from(hardest_config()).unwrap()).unwrap();
+        assert!(is_completion_of(&solution, &board));
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_logic_solver_uses_pairs() {
+        let mut singles_only = fixtures::board(fixtures::PAIRS);
+        assert!(!LogicSolver::solve_with(&mut singles_only, Pass::HiddenSingles));
+
+        let mut board = singles_only;
+        assert!(LogicSolver::solve(&mut board));
+        assert_eq!(Some(board), BacktrackingSolver::run(&fixtures::board(fixtures::PAIRS)));
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_logic_solver_uses_intersections() {
+        let mut without = fixtures::board(fixtures::INTERSECTIONS);
+        assert!(!LogicSolver::solve_with(&mut without, Pass::Pairs));
+
+        let mut board = without;
+        assert!(LogicSolver::solve(&mut board));
+        assert_eq!(Some(board), BacktrackingSolver::run(&fixtures::board(fixtures::INTERSECTIONS)));
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_logic_solver_uses_x_wing() {
+        let mut grid = CandidateGrid::new(&fixtures::board(fixtures::X_WING));
+        while LogicSolver::step(&mut grid, Pass::Triples) {}
+        assert!(grid.solved_board().is_none());
+
+        // Two columns only take 1 in rows 0 and 6, so the rest of those rows can't.
+        let cleared = [(0, 1), (0, 4), (0, 6), (6, 1)];
+        assert!(cleared.iter().all(|&cell| grid.candidates(cell).contains(&1)));
+        assert!(LogicSolver::apply_fish(&mut grid, 2));
+        assert!(cleared.iter().all(|&cell| !grid.candidates(cell).contains(&1)));
+
+        let mut board = fixtures::board(fixtures::X_WING);
+        assert!(LogicSolver::solve(&mut board));
+        assert_eq!(Some(board), BacktrackingSolver::run(&fixtures::board(fixtures::X_WING)));
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_logic_solver_uses_simple_coloring() {
+        let mut grid = CandidateGrid::new(&fixtures::board(fixtures::COLORING));
+        while LogicSolver::step(&mut grid, Pass::Swordfish) {}
+        assert!(grid.solved_board().is_none());
+
+        // (5, 6) sees both colors of a chain of conjugate 1s, so whichever color is true, it
+        // can't be 1.
+        let expected = crate::candidate_grid::Elimination { value: 1, cells: vec![(5, 6)] };
+        assert_eq!(grid.find_simple_coloring(), Some(expected));
+        assert!(LogicSolver::apply_simple_coloring(&mut grid));
+        assert!(!grid.candidates((5, 6)).contains(&1));
+
+        let mut board = fixtures::board(fixtures::COLORING);
+        assert!(LogicSolver::solve(&mut board));
+        assert_eq!(Some(board), BacktrackingSolver::run(&fixtures::board(fixtures::COLORING)));
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_solvers_agree() {
+        assert!(solvers_agree(&SudokuBoard::from(solvable_config()).unwrap()));
+        assert!(solvers_agree(&SudokuBoard::from(hardest_config()).unwrap()));
+        assert!(solvers_agree(&SudokuBoard::from(unsolvable_config()).unwrap()));
+        assert!(solvers_agree(&SudokuBoard::from([[0; 9]; 9]).unwrap()));
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_solvers_agree_on_fixtures() {
+        // Each fixture has one solution, so agreeing means every solver found that grid.
+        for (name, line) in crate::fixtures::ALL {
+            let board = crate::fixtures::board(line);
+            assert!(solvers_agree(&board), "{}", name);
+        }
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_is_completion_of() {
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        let solved = BacktrackingSolver::run(&board).unwrap();
+        assert!(is_completion_of(&solved, &board));
+        assert!(!is_completion_of(&board, &board));
+
+        // A solution that disagrees with a filled cell is rejected.
+        let mut other = board;
+        other.try_place((0, 0), 2).unwrap();
+        assert!(!is_completion_of(&solved, &other));
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        // Proper puzzles have exactly one solution.
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 2), 1);
+        let board = SudokuBoard::from(hardest_config()).unwrap();
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 2), 1);
+
+        let board = SudokuBoard::from(unsolvable_config()).unwrap();
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 2), 0);
+
+        // An empty board stops at the limit.
+        let board = SudokuBoard::from([[0; 9]; 9]).unwrap();
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 10), 10);
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 0), 0);
+    }
+
+    #[test]
+    fn test_count_solutions_two_solutions() {
+        // In the solved grid, (0, 3)/(1, 8) hold 5 and (0, 8)/(1, 3) hold 7. Blanking that
+        // rectangle lets the 5s and 7s swap, giving exactly two solutions.
+        let solved = BacktrackingSolver::run(&SudokuBoard::from(solvable_config()).unwrap()).unwrap();
+        let mut config = *solved.get_board();
+        for (r, c) in [(0, 3), (0, 8), (1, 3), (1, 8)] {
+            config[r][c] = 0;
+        }
+        let board = SudokuBoard::from(config).unwrap();
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 5), 2);
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 1), 1);
+    }
+
+    #[test]
+    fn test_solutions() {
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        let all: Vec<_> = solutions(&board).collect();
+        assert_eq!(all, vec![BacktrackingSolver::run(&board).unwrap()]);
+
+        assert_eq!(solutions(&SudokuBoard::from(unsolvable_config()).unwrap()).count(), 0);
+        let mut broken = board;
+        broken.internal_place((0, 0), 6);
+        assert_eq!(solutions(&broken).count(), 0);
+
+        // A full board is its own only solution.
+        let solved = all[0];
+        assert_eq!(solutions(&solved).collect::<Vec<_>>(), vec![solved]);
+
+        // The same rectangle as test_count_solutions_two_solutions.
+        let mut config = *solved.get_board();
+        for (r, c) in [(0, 3), (0, 8), (1, 3), (1, 8)] {
+            config[r][c] = 0;
+        }
+        let board = SudokuBoard::from(config).unwrap();
+        let both: Vec<_> = solutions(&board).collect();
+        assert_eq!(both.len(), 2);
+        assert_ne!(both[0], both[1]);
+        assert!(both.iter().all(|b| b.is_solved()));
+
+        // An empty board has billions of solutions; taking a few must still return quickly.
+        let first: HashSet<_> = solutions(&SudokuBoard::from([[0; 9]; 9]).unwrap()).take(10).collect();
+        assert_eq!(first.len(), 10);
+        assert!(first.iter().all(|b| b.is_solved()));
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_rate_difficulty() {
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        let solved = BacktrackingSolver::run(&board).unwrap();
+
+        // Blanking the diagonal of a solved grid leaves nine naked singles.
+        let mut config = *solved.get_board();
+        for (i, row) in config.iter_mut().enumerate() {
+            row[i] = 0;
+        }
+        assert_eq!(rate_difficulty(&SudokuBoard::from(config).unwrap()), Difficulty::Easy);
+
+        assert_eq!(rate_difficulty(&board), Difficulty::Medium);
+        assert_eq!(rate_difficulty(&fixtures::board(fixtures::PAIRS)), Difficulty::Hard);
+        assert_eq!(rate_difficulty(&fixtures::board(fixtures::INTERSECTIONS)), Difficulty::Hard);
+        assert_eq!(rate_difficulty(&fixtures::board(fixtures::HIDDEN_TRIPLE)), Difficulty::Hard);
+        assert_eq!(rate_difficulty(&fixtures::board(fixtures::X_WING)), Difficulty::Expert);
+        assert_eq!(rate_difficulty(&fixtures::board(fixtures::SWORDFISH)), Difficulty::Expert);
+        assert_eq!(rate_difficulty(&fixtures::board(fixtures::COLORING)), Difficulty::Master);
+        assert_eq!(rate_difficulty(&SudokuBoard::from(hardest_config()).unwrap()), Difficulty::Extreme);
+
+        assert_eq!(rate_difficulty(&SudokuBoard::from(unsolvable_config()).unwrap()), Difficulty::NotUnique);
+        assert_eq!(rate_difficulty(&SudokuBoard::from([[0; 9]; 9]).unwrap()), Difficulty::NotUnique);
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_next_hint() {
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        let expected = Hint { cell: (2, 0), value: 5, technique: Technique::NakedSingle };
+        assert_eq!(next_hint(&board), Some(expected));
+
+        // Once naked singles run out, the sample puzzle still has a hidden single.
+        let mut board = board;
+        LogicSolver::solve_with(&mut board, Pass::NakedSingles);
+        let hint = next_hint(&board).unwrap();
+        assert_eq!(hint.technique, Technique::HiddenSingle);
+        let solved = BacktrackingSolver::run(&SudokuBoard::from(solvable_config()).unwrap()).unwrap();
+        assert_eq!(solved.get(hint.cell), Some(hint.value));
+
+        // No hints where the logic solver stalls, on a solved board, or on a broken one.
+        let mut board = SudokuBoard::from(hardest_config()).unwrap();
+        LogicSolver::solve(&mut board);
+        assert_eq!(next_hint(&board), None);
+        assert_eq!(next_hint(&solved), None);
+        let mut broken = SudokuBoard::from(solvable_config()).unwrap();
+        broken.internal_place((0, 0), 6);
+        assert_eq!(next_hint(&broken), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_run_parallel() {
+        for config in [solvable_config(), hardest_config()] {
+            let board = SudokuBoard::from(config).unwrap();
+            assert_eq!(BacktrackingSolver::run_parallel(&board), BacktrackingSolver::run(&board));
+        }
+        assert_eq!(BacktrackingSolver::run_parallel(&SudokuBoard::from(unsolvable_config()).unwrap()), None);
+
+        // Any of the many solutions may come back, but it must be one.
+        let empty = SudokuBoard::from([[0; 9]; 9]).unwrap();
+        assert!(BacktrackingSolver::run_parallel(&empty).unwrap().is_solved());
+
+        let solved = BacktrackingSolver::run(&SudokuBoard::from(solvable_config()).unwrap()).unwrap();
+        assert_eq!(BacktrackingSolver::run_parallel(&solved), Some(solved));
+        let mut broken = SudokuBoard::from(solvable_config()).unwrap();
+        broken.internal_place((0, 0), 6);
+        assert_eq!(BacktrackingSolver::run_parallel(&broken), None);
+    }
+
+    #[test]
+    fn test_run_random() {
+        let empty = SudokuBoard::from([[0; 9]; 9]).unwrap();
+        let first = BacktrackingSolver::run_random(&empty, 1).unwrap();
+        assert!(first.is_solved());
+        assert_eq!(BacktrackingSolver::run_random(&empty, 1), Some(first));
+
+        let completions: HashSet<_> = (0..5).map(|seed| BacktrackingSolver::run_random(&empty, seed).unwrap()).collect();
+        assert!(completions.len() > 1);
+
+        // A puzzle with one solution always gives that solution.
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        let expected = BacktrackingSolver::run(&board);
+        assert!((0..5).all(|seed| BacktrackingSolver::run_random(&board, seed) == expected));
+        assert_eq!(BacktrackingSolver::run_random(&SudokuBoard::from(unsolvable_config()).unwrap(), 3), None);
+    }
+
+    #[test]
+    fn test_run_sized() {
+        use crate::sized_board::{Board16, Board4, Board9};
+
+        let puzzle = Board4::from(&[1, 0, 0, 0, 0, 0, 3, 0, 0, 4, 0, 0, 0, 0, 0, 2]).unwrap();
+        let solved = BacktrackingSolver::run_sized(&puzzle).unwrap();
+        assert_eq!(solved.as_flat(), &[1, 3, 2, 4, 4, 2, 3, 1, 2, 4, 1, 3, 3, 1, 4, 2]);
+        assert!(solved.is_solved());
+
+        // The 4 in the last column leaves nothing for the end of the first row.
+        let stuck = Board4::from(&[1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0]).unwrap();
+        assert_eq!(BacktrackingSolver::run_sized(&stuck), None);
+
+        // 9x9 agrees with the fixed-size solver on a puzzle with one solution.
+        let classic = SudokuBoard::from(hardest_config()).unwrap();
+        let solved = BacktrackingSolver::run_sized(&Board9::from_classic(&classic).unwrap()).unwrap();
+        assert_eq!(solved.as_flat(), &BacktrackingSolver::run(&classic).unwrap().as_flat()[..]);
+
+        assert!(BacktrackingSolver::run_sized(&Board16::from(&[0; 256]).unwrap()).unwrap().is_solved());
+    }
+
+    // The recursive search that `search` replaced, kept to check the two agree.
+    fn recursive_solve(board: &mut SudokuBoard, stats: &mut SolveStats, depth: u32) -> bool {
+        stats.max_depth = stats.max_depth.max(depth);
+        if let Some(empty_cell) = BacktrackingSolver::find_most_constrained_cell(board) {
+            if (1..=9).filter(|&num| board.is_placement_valid(empty_cell, num)).count() > 1 {
+                stats.guesses += 1;
+            }
+            for num in 1..=9 {
+                if board.is_placement_valid(empty_cell, num) {
+                    board.place_fast(empty_cell, num);
+                    stats.nodes += 1;
+                    if recursive_solve(board, stats, depth + 1) {
+                        return true;
+                    }
+                    board.unplace_fast(empty_cell, num);
+                    stats.backtracks += 1;
+                }
+            }
+            false
+        } else {
+            true
+        }
+    }
+
+    #[test]
+    fn test_search_matches_recursive_solve() {
+        for config in [solvable_config(), hardest_config(), unsolvable_config(), [[0; 9]; 9]] {
+            let board = SudokuBoard::from(config).unwrap();
+            let (mut iterative, mut recursive) = (board, board);
+            let (mut iterative_stats, mut recursive_stats) = (SolveStats::default(), SolveStats::default());
+            let solved = BacktrackingSolver::search(&mut iterative, &mut iterative_stats);
+            assert_eq!(solved, recursive_solve(&mut recursive, &mut recursive_stats, 0));
+            assert_eq!(iterative, recursive);
+            assert_eq!(iterative_stats, recursive_stats);
+        }
+
+        // A failed search leaves the board as it found it.
+        let board = SudokuBoard::from(unsolvable_config()).unwrap();
+        let mut searched = board;
+        assert!(!BacktrackingSolver::search(&mut searched, &mut SolveStats::default()));
+        assert_eq!(searched, board);
+    }
+
+    #[test]
+    fn test_step_solver() {
+        for config in [solvable_config(), hardest_config()] {
+            let board = SudokuBoard::from(config).unwrap();
+            let mut solver = StepSolver::new(&board);
+            let (mut places, mut backtracks) = (0, 0);
+            while let Some(step) = solver.step() {
+                match step {
+                    SolverStep::Place { cell, num } => {
+                        assert_eq!(solver.board().get(cell), Some(num));
+                        places += 1;
+                    }
+                    SolverStep::Backtrack { cell } => {
+                        assert_eq!(solver.board().get(cell), Some(0));
+                        backtracks += 1;
+                    }
+                }
+            }
+
+            let (solved, stats) = BacktrackingSolver::run_with_stats(&board);
+            assert_eq!(Some(*solver.board()), solved);
+            assert_eq!((places, backtracks), (stats.nodes, stats.backtracks));
+            assert_eq!(solver.step(), None);
+        }
+
+        let board = SudokuBoard::from(unsolvable_config()).unwrap();
+        let mut solver = StepSolver::new(&board);
+        // The first row's last cell has no candidates, so the search fails straight away.
+        assert_eq!(solver.step(), None);
+        assert_eq!(*solver.board(), board);
+
+        let mut broken = SudokuBoard::from(solvable_config()).unwrap();
+        broken.internal_place((0, 0), 6);
+        assert_eq!(StepSolver::new(&broken).step(), None);
+    }
+
     #[test]
     fn test_backtrack_solver_fail() {
         let board: SudokuBoard = SudokuBoard::from(unsolvable_config()).unwrap();
         assert!(BacktrackingSolver::run(&board).is_none());
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_solve_trace_replays_to_solution() {
+        for config in [solvable_config(), hardest_config()] {
+            let board = SudokuBoard::from(config).unwrap();
+            let trace = solve_trace(&board).unwrap();
+
+            let mut replay = board;
+            for step in &trace {
+                let cell = (step.cell[0], step.cell[1]);
+                match step.kind {
+                    StepKind::Place => replay.try_place(cell, step.value).unwrap(),
+                    StepKind::Backtrack => replay.unplace_fast(cell, step.value),
+                }
+            }
+            assert_eq!(replay.get_board(), BacktrackingSolver::run(&board).unwrap().get_board());
+        }
+
+        // The sample puzzle falls to singles alone; the hardest needs the search.
+        let easy = solve_trace(&SudokuBoard::from(solvable_config()).unwrap()).unwrap();
+        assert!(easy.iter().all(|step| step.technique.is_some()));
+        let hard = solve_trace(&SudokuBoard::from(hardest_config()).unwrap()).unwrap();
+        assert!(hard.iter().any(|step| step.kind == StepKind::Backtrack));
+
+        assert_eq!(solve_trace(&SudokuBoard::from(unsolvable_config()).unwrap()), None);
+    }
+
+    #[cfg(all(feature = "extra_solvers", feature = "serde"))]
+    #[test]
+    fn test_solve_trace_json_round_trip() {
+        let board = SudokuBoard::from(solvable_config()).unwrap();
+        let json = solve_trace_json(&board).unwrap();
+        assert!(json.starts_with(r#"[{"cell":[2,0],"value":5,"kind":"place","technique":"naked_single"}"#));
+
+        let parsed: Vec<TraceStep> = serde_json::from_str(&json).unwrap();
+        assert_eq!(Some(parsed), solve_trace(&board));
+        assert_eq!(solve_trace_json(&SudokuBoard::from(unsolvable_config()).unwrap()), None);
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_dancing_links_solver() {
+        for config in [solvable_config(), hardest_config()] {
+            let board = SudokuBoard::from(config).unwrap();
+            let solved = DancingLinksSolver::run(&board).unwrap();
+            assert_eq!(solved, BacktrackingSolver::run(&board).unwrap());
+        }
+
+        // Player entries are kept, and a wrong one makes the board unsolvable.
+        let mut board = SudokuBoard::from(solvable_config()).unwrap();
+        let solved = BacktrackingSolver::run(&board).unwrap();
+        board.try_place((0, 0), solved.get((0, 0)).unwrap()).unwrap();
+        assert_eq!(DancingLinksSolver::run(&board), Some(solved));
+        board.try_place((0, 0), 0).unwrap();
+        board.try_place((0, 0), 2).unwrap();
+        assert_eq!(DancingLinksSolver::run(&board), None);
+
+        assert_eq!(DancingLinksSolver::run(&SudokuBoard::from(unsolvable_config()).unwrap()), None);
+
+        let mut board = SudokuBoard::from(solvable_config()).unwrap();
+        board.internal_place((0, 0), 6);
+        assert_eq!(DancingLinksSolver::run(&board), None);
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_dancing_links_arena_matches_run() {
+        // Failed and aborted solves in between mustn't leave anything behind in the arena.
+        let mut clashing = SudokuBoard::from(solvable_config()).unwrap();
+        clashing.internal_place((0, 0), 6);
+        let mut boards: Vec<_> = fixtures::ALL.iter().map(|&(_, line)| fixtures::board(line)).collect();
+        boards.insert(2, SudokuBoard::from(unsolvable_config()).unwrap());
+        boards.insert(4, clashing);
+        boards.push(SudokuBoard::from([[0; 9]; 9]).unwrap());
+
+        let mut solver = DancingLinksSolver::with_arena();
+        for _ in 0..2 {
+            for board in &boards {
+                assert_eq!(solver.solve(board), DancingLinksSolver::run(board));
+            }
+        }
+    }
+
+    #[cfg(feature = "extra_solvers")]
+    #[test]
+    fn test_unavoidable_sets() {
+        let solution = BacktrackingSolver::run(&fixtures::board(fixtures::SINGLES)).unwrap();
+        let blank = |cells: &[(u8, u8)]| {
+            let mut config = *solution.get_board();
+            for &(r, c) in cells {
+                config[r as usize][c as usize] = 0;
+            }
+            SudokuBoard::from(config).unwrap()
+        };
+
+        let sets = unavoidable_sets(&solution, 6);
+        assert!(sets.windows(2).all(|pair| pair[0].len() <= pair[1].len()));
+        for set in &sets {
+            assert!(set.len() <= 6);
+            // Blanking the whole set allows a second completion, but keeping any one of its
+            // cells as a clue doesn't.
+            assert_eq!(BacktrackingSolver::count_solutions(&blank(set), 2), 2);
+            for kept in set {
+                let rest: Vec<_> = set.iter().copied().filter(|cell| cell != kept).collect();
+                assert_eq!(BacktrackingSolver::count_solutions(&blank(&rest), 2), 1);
+            }
+        }
+
+        // Nothing smaller than a rectangle of two numbers can be swapped.
+        let rectangles = unavoidable_sets(&solution, 4);
+        assert_eq!(rectangles[0], vec![(0, 3), (0, 8), (1, 3), (1, 8)]);
+        assert_eq!(rectangles, sets[..rectangles.len()]);
+        assert!(sets.len() > rectangles.len());
+        assert!(unavoidable_sets(&solution, 3).is_empty());
+
+        assert!(unavoidable_sets(&fixtures::board(fixtures::SINGLES), 6).is_empty());
+    }
+
+    // Solvable puzzles for property tests: a random complete grid with a random subset of
+    // its cells kept as givens. Every such puzzle has at least one solution.
+    fn solvable_puzzle() -> impl Strategy<Value = SudokuBoard> {
+        (any::<u64>(), proptest::collection::vec(any::<bool>(), 81)).prop_map(|(seed, keep)| {
+            let solved = BacktrackingSolver::run_random(&SudokuBoard::empty(), seed).unwrap();
+            let cells: Vec<u8> =
+                solved.as_flat().iter().zip(&keep).map(|(&value, &kept)| if kept { value } else { 0 }).collect();
+            SudokuBoard::from_flat(&cells).unwrap()
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_solved_board_is_valid_complete_grid(puzzle in solvable_puzzle()) {
+            prop_assert!(BacktrackingSolver::count_solutions(&puzzle, 2) >= 1);
+
+            let solved = BacktrackingSolver::run(&puzzle).unwrap();
+            prop_assert!(SudokuBoard::is_valid_config(solved.get_board()));
+            prop_assert!(!solved.as_flat().contains(&0));
+            for (cell, value) in puzzle.iter_cells().filter(|&(_, value)| value != 0) {
+                prop_assert_eq!(solved.get(cell), Some(value));
+                prop_assert_eq!(solved.is_given(cell), Some(true));
+            }
+        }
+    }
+}
+
+// One case per technique: a puzzle that every easier technique leaves stuck, where the
+// technique under test is what gets it moving again and takes out exactly the expected
+// candidates. A finder that over- or under-triggers shows up here first.
+#[cfg(all(test, feature = "extra_solvers"))]
+mod technique_tests {
+    use super::*;
+    use crate::fixtures;
+
+    // Checks that `easier` stalls on `line` but `pass` solves it, and returns the grid where
+    // `easier` gets stuck.
+    fn stalled(line: &str, easier: Pass, pass: Pass) -> CandidateGrid {
+        let board = fixtures::board(line);
+        assert!(!LogicSolver::solve_with(&mut board.clone(), easier));
+        assert!(LogicSolver::solve_with(&mut board.clone(), pass));
+        let mut grid = CandidateGrid::new(&board);
+        while LogicSolver::step(&mut grid, easier) {}
+        grid
+    }
+
+    // The candidates one `apply` call removes, in row-major order.
+    fn removed(grid: &CandidateGrid, apply: impl Fn(&mut CandidateGrid) -> bool) -> Vec<Placement> {
+        let mut after = *grid;
+        if !apply(&mut after) {
+            return Vec::new();
+        }
+        grid.board()
+            .iter_empty()
+            .flat_map(|cell| grid.candidates(cell).into_iter().map(move |num| (cell, num)))
+            .filter(|&(cell, num)| !after.candidates(cell).contains(&num))
+            .collect()
+    }
+
+    #[test]
+    fn test_naked_single() {
+        let grid = CandidateGrid::new(&fixtures::board(fixtures::SINGLES));
+        assert_eq!(LogicSolver::find_naked_single(&grid), Some(((2, 0), 5)));
+    }
+
+    #[test]
+    fn test_hidden_single() {
+        let grid = stalled(fixtures::SINGLES, Pass::NakedSingles, Pass::HiddenSingles);
+        assert_eq!(LogicSolver::find_naked_single(&grid), None);
+        assert_eq!(LogicSolver::find_hidden_single(&grid), Some(((0, 0), 3)));
+    }
+
+    #[test]
+    fn test_naked_pair() {
+        // (5, 0) and (8, 0) both have only 1 and 3 left, so the rest of column 0 can have neither.
+        let grid = stalled(fixtures::NAKED_PAIR, Pass::HiddenSingles, Pass::Pairs);
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_hidden_set(grid, 2)), []);
+        let expected = [((1, 0), 3), ((2, 0), 1), ((3, 0), 1), ((6, 0), 1), ((6, 0), 3)];
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_naked_set(grid, 2)), expected);
+    }
+
+    #[test]
+    fn test_hidden_pair() {
+        let grid = stalled(fixtures::HIDDEN_PAIR, Pass::HiddenSingles, Pass::Pairs);
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_naked_set(grid, 2)), []);
+        let expected = [((3, 0), 3), ((3, 3), 3), ((3, 3), 9)];
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_hidden_set(grid, 2)), expected);
+    }
+
+    #[test]
+    fn test_pointing() {
+        let grid = stalled(fixtures::POINTING, Pass::Pairs, Pass::Intersections);
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_intersection(grid, false)), []);
+        let expected = [((3, 0), 9), ((6, 0), 9)];
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_intersection(grid, true)), expected);
+    }
+
+    #[test]
+    fn test_box_line_reduction() {
+        let grid = stalled(fixtures::BOX_LINE, Pass::Pairs, Pass::Intersections);
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_intersection(grid, true)), []);
+        let expected = [((0, 0), 3), ((0, 1), 3)];
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_intersection(grid, false)), expected);
+    }
+
+    #[test]
+    fn test_naked_triple() {
+        let grid = stalled(fixtures::NAKED_TRIPLE, Pass::Intersections, Pass::Triples);
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_hidden_set(grid, 3)), []);
+        let expected = [((4, 5), 3), ((5, 5), 9), ((6, 5), 9), ((7, 5), 3), ((7, 5), 9), ((8, 5), 3)];
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_naked_set(grid, 3)), expected);
+    }
+
+    #[test]
+    fn test_hidden_triple() {
+        let grid = stalled(fixtures::HIDDEN_TRIPLE, Pass::Intersections, Pass::Triples);
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_naked_set(grid, 3)), []);
+        let expected = [((6, 7), 2), ((7, 8), 1), ((7, 8), 7)];
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_hidden_set(grid, 3)), expected);
+    }
+
+    #[test]
+    fn test_x_wing() {
+        let grid = stalled(fixtures::X_WING, Pass::Triples, Pass::XWing);
+        let expected = [((0, 1), 1), ((0, 4), 1), ((0, 6), 1), ((6, 1), 1)];
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_fish(grid, 2)), expected);
+    }
+
+    #[test]
+    fn test_swordfish() {
+        let grid = stalled(fixtures::SWORDFISH, Pass::XWing, Pass::Swordfish);
+        let expected = [((1, 0), 5), ((5, 0), 5), ((5, 4), 5), ((5, 5), 5)];
+        assert_eq!(removed(&grid, |grid| LogicSolver::apply_fish(grid, 3)), expected);
+    }
+
+    #[test]
+    fn test_simple_coloring() {
+        let grid = stalled(fixtures::COLORING, Pass::Swordfish, Pass::Coloring);
+        assert_eq!(removed(&grid, LogicSolver::apply_simple_coloring), [((5, 6), 1)]);
+    }
+}
@@ -0,0 +1,56 @@
+// Wasm Module
+use crate::generator;
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::BacktrackingSolver;
+use wasm_bindgen::prelude::*;
+
+// The browser has no system clock for `generate` to seed from, so ask JavaScript instead.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Math, js_name = random)]
+    fn js_random() -> f64;
+}
+
+/// Solves a puzzle given as an 81-character line (`0` or `.` for empty cells), returning the
+/// solution in the same form. Returns undefined if the line doesn't parse or has no solution.
+#[wasm_bindgen]
+pub fn solve_line(puzzle: &str) -> Option<String> {
+    let board: SudokuBoard = puzzle.parse().ok()?;
+    BacktrackingSolver::run(&board).map(|solved| solved.to_line())
+}
+
+/// Generates a puzzle with a unique solution and about `clues` givens, as an 81-character
+/// line with `0` for empty cells.
+#[wasm_bindgen]
+pub fn generate_line(clues: usize) -> String {
+    #[cfg(target_arch = "wasm32")]
+    let board = generator::generate_seeded(clues, (js_random() * u64::MAX as f64) as u64);
+    #[cfg(not(target_arch = "wasm32"))]
+    let board = generator::generate(clues);
+    board.to_line()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::SINGLES as PUZZLE;
+
+    #[test]
+    fn test_solve_line() {
+        let solved = solve_line(PUZZLE).unwrap();
+        assert_eq!(solved.len(), 81);
+        assert!(solved.parse::<SudokuBoard>().unwrap().is_solved());
+        assert!(PUZZLE.chars().zip(solved.chars()).all(|(given, cell)| given == '0' || given == cell));
+
+        assert_eq!(solve_line("123"), None);
+        assert_eq!(solve_line(&PUZZLE.replacen('0', "6", 1)), None);
+    }
+
+    #[test]
+    fn test_generate_line() {
+        let line = generate_line(30);
+        let board: SudokuBoard = line.parse().unwrap();
+        assert_eq!(BacktrackingSolver::count_solutions(&board, 2), 1);
+    }
+}